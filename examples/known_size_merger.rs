@@ -12,7 +12,7 @@ fn generate_known_image() -> BufferedImage<Rgba<u8>> {
     BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255, 0, 0, 255]))
 }
 
-fn main() -> () {
+fn main() {
     // Generate a image we can paste to our canvas. In a real application, this may be an opened
     // image file or buffer of some sort. For the sake of example, the constants IMAGE_WIDTH and IMAGE_HEIGHT
     // will represent our known image dimensions.
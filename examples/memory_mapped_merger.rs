@@ -0,0 +1,46 @@
+extern crate image_merger;
+
+use image_merger::*;
+use memmap2::MmapMut;
+use std::fs::OpenOptions;
+
+const IMAGE_WIDTH: u32 = 100;
+const IMAGE_HEIGHT: u32 = 100;
+const IMAGES_PER_ROW: u32 = 10;
+const TOTAL_IMAGES: u32 = 100;
+
+fn main() {
+    // Very large canvases may not fit in memory, so this example keeps the canvas in a file instead. The operating system
+    // pages the canvas in and out of memory as it is written to.
+    let width = IMAGE_WIDTH * IMAGES_PER_ROW;
+    let height = IMAGE_HEIGHT * TOTAL_IMAGES.div_ceil(IMAGES_PER_ROW);
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open("examples/memory_mapped_merger.raw")
+        .unwrap();
+    file.set_len((width * height * 4) as u64).unwrap();
+
+    // The memory map can back the canvas by borrowing it as a plain slice of subpixels.
+    let mut mmap = unsafe { MmapMut::map_mut(&file) }.unwrap();
+    let mut merger: KnownSizeMerger<Rgba<u8>, &mut [u8]> = KnownSizeMerger::new_from_raw(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        None,
+        &mut mmap[..],
+    )
+    .unwrap();
+
+    // Images backed by a Vec, such as decoded image files, can be pushed with "push()" like any other image.
+    let image = BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255, 0, 0, 255]));
+    for _ in 0..TOTAL_IMAGES {
+        merger.push(&image);
+    }
+
+    // Make sure every pixel has reached the file before exiting.
+    drop(merger);
+    mmap.flush().unwrap();
+}
@@ -1,9 +1,9 @@
 use super::core::Image;
-use image::Pixel;
+use image::{ImageBuffer, Pixel};
 use std::{
     cell::UnsafeCell,
     marker::{Send, Sync},
-    ops::Deref,
+    ops::{Deref, DerefMut},
 };
 
 /// A struct that allows multiple mutable references to an underlying image's data buffer. This is an
@@ -19,6 +19,17 @@ pub struct Handout<'a, P: Pixel, U: image::GenericImage<Pixel = P>> {
     ic: &'a ImageCell<P, U>,
     x: u32,
     y: u32,
+    len: u32, // The number of pixels along the row the handout covers.
+}
+
+/// Represents a handout of a rectangular region of an image cell. This struct is used to write whole rows of the region
+/// without a mutable reference to the underlying image, which avoids the overhead of handing out every pixel on its own.
+pub struct RegionHandout<'a, P: Pixel, U: image::GenericImage<Pixel = P>> {
+    ic: &'a ImageCell<P, U>,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
 }
 
 impl<P: Pixel, U: image::GenericImage<Pixel = P>> ImageCell<P, U> {
@@ -59,8 +70,84 @@ impl<P: Pixel, U: image::GenericImage<Pixel = P>> ImageCell<P, U> {
     /// let mut handout = unsafe { cell.request_handout(0, 0) };
     /// handout.put_pixel(Rgb([255, 255, 255]));
     /// ```
-    pub unsafe fn request_handout(&self, x: u32, y: u32) -> Handout<P, U> {
-        Handout { ic: self, x, y }
+    pub unsafe fn request_handout(&self, x: u32, y: u32) -> Handout<'_, P, U> {
+        Handout {
+            ic: self,
+            x,
+            y,
+            len: 1,
+        }
+    }
+
+    /// Requests a handout of `len` pixels along a row of the underlying image, starting at the given coordinates. This allows
+    /// whole runs of pixels to be written at once with `put_row`, which is much faster than writing them one at a time.
+    /// # Safety
+    /// This function is unsafe for the same reasons as `request_handout`. It is up to the caller to ensure that no two threads
+    /// are trying to write to the same place in the underlying image's data buffer.
+    ///
+    /// # Arguments
+    /// * `x` - The x coordinate of the first pixel to request a handout for.
+    /// * `y` - The y coordinate of the row to request a handout for.
+    /// * `len` - The number of pixels the handout covers.
+    /// # Returns
+    /// A handout that can be used to write to the underlying image's data buffer.
+    /// # Example
+    /// ```
+    /// use image_merger::{Rgb, raw::ImageCell, Image};
+    /// use image::ImageBuffer;
+    ///
+    /// let buf: ImageBuffer<Rgb<u8>, Vec<u8>> = ImageBuffer::new(100, 100);
+    /// let cell = ImageCell::new(Image::from(buf));
+    /// let mut handout = unsafe { cell.request_row_handout(0, 0, 2) };
+    /// handout.put_row(&[255, 255, 255, 0, 0, 0]);
+    /// ```
+    pub unsafe fn request_row_handout(&self, x: u32, y: u32, len: u32) -> Handout<'_, P, U> {
+        Handout {
+            ic: self,
+            x,
+            y,
+            len,
+        }
+    }
+
+    /// Requests a handout of a `width` by `height` region of the underlying image, with its top left at the given coordinates.
+    /// The rows of the region can then be written directly as slices with `row_mut`, which makes it possible to write a custom
+    /// merger that pastes whole tiles in parallel.
+    /// # Safety
+    /// This function is unsafe for the same reasons as `request_handout`. It is up to the caller to ensure that no two
+    /// handouts that are written to at the same time overlap.
+    ///
+    /// # Arguments
+    /// * `x` - The x coordinate of the top left of the region.
+    /// * `y` - The y coordinate of the top left of the region.
+    /// * `width` - The width of the region.
+    /// * `height` - The height of the region.
+    /// # Returns
+    /// A handout that can be used to write to the region of the underlying image's data buffer.
+    /// # Example
+    /// ```
+    /// use image_merger::{Rgb, raw::ImageCell, Image};
+    /// use image::ImageBuffer;
+    ///
+    /// let buf: ImageBuffer<Rgb<u8>, Vec<u8>> = ImageBuffer::new(100, 100);
+    /// let cell = ImageCell::new(Image::from(buf));
+    /// let mut region = unsafe { cell.request_region(10, 10, 2, 2) };
+    /// region.row_mut(0).copy_from_slice(&[255, 255, 255, 0, 0, 0]);
+    /// ```
+    pub unsafe fn request_region(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> RegionHandout<'_, P, U> {
+        RegionHandout {
+            ic: self,
+            x,
+            y,
+            width,
+            height,
+        }
     }
 }
 
@@ -94,3 +181,137 @@ impl<'a, P: Pixel, U: image::GenericImage<Pixel = P>> Handout<'a, P, U> {
         image.unsafe_put_pixel(self.x, self.y, pixel);
     }
 }
+
+impl<'a, P, Container> Handout<'a, P, ImageBuffer<P, Container>>
+where
+    P: Pixel,
+    Container: DerefMut<Target = [P::Subpixel]>,
+{
+    /// Copies a run of pixels onto the row covered by the handout, starting at the handout's coordinates.
+    /// # Arguments
+    /// * `pixels` - The subpixels to copy, with the channels of each pixel interleaved.
+    /// # Panics
+    /// This function will panic if `pixels` does not hold exactly as many pixels as the handout covers, or if the run extends
+    /// past the end of the row.
+    pub fn put_row(&mut self, pixels: &[P::Subpixel]) {
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        assert_eq!(
+            pixels.len(),
+            self.len as usize * channels,
+            "The pixels must fill the whole handout!"
+        );
+
+        let image = self.ic.get_image_mut();
+        let (width, height) = image.dimensions();
+        assert!(
+            self.y < height && self.x + self.len <= width,
+            "The handout is out of the image's bounds!"
+        );
+
+        let start = (self.y as usize * width as usize + self.x as usize) * channels;
+        let buffer: &mut [P::Subpixel] = image;
+        buffer[start..start + pixels.len()].copy_from_slice(pixels);
+    }
+}
+
+impl<'a, P, Container> RegionHandout<'a, P, ImageBuffer<P, Container>>
+where
+    P: Pixel,
+    Container: DerefMut<Target = [P::Subpixel]>,
+{
+    /// Returns the dimensions, (width, height), of the region.
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Returns a row of the region as a mutable slice, with the channels of each pixel interleaved.
+    /// # Arguments
+    /// * `row` - The row to return, relative to the top of the region.
+    /// # Panics
+    /// This function will panic if the row is not within the region, or if the region extends past the edge of the image.
+    pub fn row_mut(&mut self, row: u32) -> &mut [P::Subpixel] {
+        assert!(row < self.height, "The row is out of the region's bounds!");
+
+        let image = self.ic.get_image_mut();
+        let (width, height) = image.dimensions();
+        assert!(
+            self.x + self.width <= width && self.y + self.height <= height,
+            "The handout is out of the image's bounds!"
+        );
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let start = ((self.y + row) as usize * width as usize + self.x as usize) * channels;
+        let buffer: &mut [P::Subpixel] = image;
+        &mut buffer[start..start + self.width as usize * channels]
+    }
+
+    /// Puts a pixel at the given coordinates within the region.
+    /// # Arguments
+    /// * `x` - The x coordinate of the pixel, relative to the left of the region.
+    /// * `y` - The y coordinate of the pixel, relative to the top of the region.
+    /// * `pixel` - The pixel to place.
+    /// # Panics
+    /// This function will panic if the coordinates are not within the region.
+    pub fn put_pixel(&mut self, x: u32, y: u32, pixel: P) {
+        assert!(x < self.width, "The pixel is out of the region's bounds!");
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let start = x as usize * channels;
+        self.row_mut(y)[start..start + channels].copy_from_slice(pixel.channels());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn test_put_row() {
+        let buf: ImageBuffer<Rgb<u8>, Vec<u8>> = ImageBuffer::new(4, 3);
+        let cell = ImageCell::new(Image::from(buf));
+
+        let row: Vec<u8> = (0..12).collect();
+        let mut handout = unsafe { cell.request_row_handout(0, 1, 4) };
+        handout.put_row(&row);
+
+        let image = cell.into_inner();
+        for x in 0..4 {
+            let i = x as u8 * 3;
+            assert_eq!(*image.get_pixel(x, 1), Rgb([i, i + 1, i + 2]));
+            assert_eq!(*image.get_pixel(x, 0), Rgb([0, 0, 0]));
+            assert_eq!(*image.get_pixel(x, 2), Rgb([0, 0, 0]));
+        }
+    }
+
+    #[test]
+    fn test_request_region() {
+        let buf: ImageBuffer<Rgb<u8>, Vec<u8>> = ImageBuffer::new(8, 6);
+        let cell = ImageCell::new(Image::from(buf));
+
+        // Fill the left and right halves of the middle rows from two threads at once.
+        std::thread::scope(|scope| {
+            for (x, value) in [(0, 100), (4, 200)] {
+                let mut region = unsafe { cell.request_region(x, 1, 4, 4) };
+                scope.spawn(move || {
+                    let (width, height) = region.dimensions();
+                    for row in 0..height {
+                        region.row_mut(row).fill(value);
+                    }
+                    region.put_pixel(width - 1, height - 1, Rgb([1, 2, 3]));
+                });
+            }
+        });
+
+        let image = cell.into_inner();
+        for (x, y, pixel) in image.enumerate_pixels() {
+            let expected = match (x, y) {
+                (_, 0) | (_, 5) => Rgb([0, 0, 0]),
+                (3, 4) | (7, 4) => Rgb([1, 2, 3]),
+                (0..=3, _) => Rgb([100, 100, 100]),
+                _ => Rgb([200, 200, 200]),
+            };
+            assert_eq!(*pixel, expected, "pixel ({x}, {y})");
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    io::{Read, Seek},
+    ops::{Deref, DerefMut},
+};
 
 use image::{ImageBuffer, ImageFormat, Luma, LumaA, Pixel, Rgb, Rgba};
 
@@ -24,7 +27,7 @@ pub struct Image<P: Pixel, U: image::GenericImage<Pixel = P>> {
 impl<P: Pixel, U: image::GenericImage<Pixel = P>> Image<P, U> {
     /// Returns the capacity of the underlying image's data buffer.
     pub fn capacity(&self) -> usize {
-        return self.underlying.pixels().count() * <P as Pixel>::CHANNEL_COUNT as usize;
+        self.underlying.pixels().count() * <P as Pixel>::CHANNEL_COUNT as usize
     }
 
     /// Consumes the image and returns the underlying image buffer.
@@ -68,8 +71,70 @@ impl<P: Pixel> Image<P, ImageBuffer<P, Vec<P::Subpixel>>> {
             underlying: ImageBuffer::from_pixel(width, height, pixel),
         }
     }
+
+    /// Creates a checkerboard of `square` by `square` pixel squares, starting with `a` in the top left corner and alternating
+    /// with `b`. The content is deterministic, which makes it useful for verifying merges in tests and examples.
+    /// # Arguments
+    /// * `width` - The width of the image.
+    /// * `height` - The height of the image.
+    /// * `square` - The side length of each square.
+    /// * `a` - The color of the top left square, and every square an even number of squares away from it.
+    /// * `b` - The color of every other square.
+    /// # Panics
+    /// This function will panic if `square` is 0.
+    #[cfg(feature = "testing")]
+    pub fn test_checkerboard(width: u32, height: u32, square: u32, a: P, b: P) -> Self {
+        assert!(
+            square > 0,
+            "The squares of a checkerboard can not be empty!"
+        );
+
+        Self {
+            underlying: ImageBuffer::from_fn(width, height, |x, y| {
+                match (x / square + y / square) % 2 {
+                    0 => a,
+                    _ => b,
+                }
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "testing")]
+impl Image<Rgba<u8>, ImageBuffer<Rgba<u8>, Vec<u8>>> {
+    /// Creates an opaque gradient where the pixel at (x, y) is `Rgba([x as u8, y as u8, (x + y) as u8, 255])`, so each channel
+    /// wraps around every 256 pixels. Every pixel within a 256 by 256 region is unique, which makes misplaced or flipped
+    /// pixels easy to spot when verifying merges in tests and examples.
+    /// # Arguments
+    /// * `width` - The width of the image.
+    /// * `height` - The height of the image.
+    pub fn test_gradient(width: u32, height: u32) -> Self {
+        Self {
+            underlying: ImageBuffer::from_fn(width, height, |x, y| {
+                Rgba([x as u8, y as u8, (x + y) as u8, 255])
+            }),
+        }
+    }
+}
+
+impl<P: AlphaPixel + Sync> Image<P, ImageBuffer<P, Vec<u8>>> {
+    /// Creates a new image with straight alpha from an image with premultiplied alpha, reversing
+    /// [to_premultiplied](crate::Merger::to_premultiplied). Fully transparent pixels become transparent black, and colors
+    /// may be off by one from the originals due to rounding.
+    /// # Arguments
+    /// * `image` - The premultiplied image to convert.
+    pub fn from_premultiplied(image: &Self) -> Self {
+        crate::functions::unpremultiply(image)
+    }
 }
 
+/// A pixel type with 8-bit subpixels whose last channel is alpha, such as [Rgba<u8>](image::Rgba) and
+/// [LumaA<u8>](image::LumaA). This marks the pixel types that can be converted to and from premultiplied alpha.
+pub trait AlphaPixel: Pixel<Subpixel = u8> {}
+
+impl AlphaPixel for Rgba<u8> {}
+impl AlphaPixel for LumaA<u8> {}
+
 /// Dereferences to the underlying image.
 ///
 /// # Type Parameters
@@ -151,6 +216,32 @@ where
     fn from_with_format(container: Container, format: ImageFormat) -> Self;
 }
 
+/// A fallible version of [FromWithFormat](FromWithFormat), which returns an error instead of panicking when the given container
+/// cannot be decoded.
+/// # Type Parameters
+/// * `Container` - The container type. This must be dereferenceable to a slice of bytes.
+pub trait TryFromWithFormat<Container>: Sized
+where
+    Container: Deref<Target = [u8]>,
+{
+    /// Attempts to transform the given container and image format into an Image.
+    /// # Arguments
+    /// * `container` - The container to transform into an Image.
+    /// * `format` - The format of the image.
+    /// # Returns
+    /// An [Image](Image) with the given pixel and buffer type, or the [image::ImageError](image::ImageError) that occurred while
+    /// decoding the container.
+    /// # Example
+    /// ```
+    /// use image_merger::{TryFromWithFormat, Rgba, BufferedImage};
+    ///
+    /// let container = vec![0, 0, 0, 255, 255, 255, 255, 255];
+    /// let image = BufferedImage::<Rgba<u8>>::try_from_with_format(container, image::ImageFormat::Png);
+    /// assert!(image.is_err());
+    /// ```
+    fn try_from_with_format(container: Container, format: ImageFormat) -> image::ImageResult<Self>;
+}
+
 macro_rules! impl_from_with_format {
     ($px_type:ident, $channel_type:ty, $to_fn:ident) => {
         #[doc = concat!(
@@ -175,10 +266,46 @@ macro_rules! impl_from_with_format {
             Container: Deref<Target = [u8]>,
         {
             fn from_with_format(container: Container, format: ImageFormat) -> Self {
-                let dyn_image = image::load_from_memory_with_format(&container, format).unwrap();
+                Self::try_from_with_format(container, format)
+                    .expect("Could not decode the container with the given format!")
+            }
+        }
+
+        impl<Container> TryFromWithFormat<Container>
+            for Image<
+                $px_type<$channel_type>,
+                ImageBuffer<$px_type<$channel_type>, Vec<$channel_type>>,
+            >
+        where
+            Container: Deref<Target = [u8]>,
+        {
+            fn try_from_with_format(
+                container: Container,
+                format: ImageFormat,
+            ) -> image::ImageResult<Self> {
+                let dyn_image = image::load_from_memory_with_format(&container, format)?;
                 let img = dyn_image.$to_fn();
 
-                Self::from(img)
+                Ok(Self::from(img))
+            }
+        }
+
+        impl Image<$px_type<$channel_type>, ImageBuffer<$px_type<$channel_type>, Vec<$channel_type>>> {
+            /// Decodes an Image from the given reader, without buffering the whole source into a container first.
+            /// # Arguments
+            /// * `reader` - The reader to decode the image from.
+            /// * `format` - The format of the image.
+            /// # Returns
+            /// An [Image](Image) with the given pixel and buffer type, or the [image::ImageError](image::ImageError) that occurred
+            /// while decoding the reader.
+            pub fn from_reader<R: Read + Seek>(
+                reader: R,
+                format: ImageFormat,
+            ) -> image::ImageResult<Self> {
+                let dyn_image = image::load(std::io::BufReader::new(reader), format)?;
+                let img = dyn_image.$to_fn();
+
+                Ok(Self::from(img))
             }
         }
     };
@@ -197,3 +324,7 @@ impl_from_with_format!(Luma, u16, into_luma16);
 
 impl_from_with_format!(LumaA, u8, into_luma_alpha8);
 impl_from_with_format!(LumaA, u16, into_luma_alpha16);
+
+// `image` has no `into_luma32f` or `into_luma_alpha32f`, so the grayscale float buffers are converted with a copy.
+impl_from_with_format!(Luma, f32, to_luma32f);
+impl_from_with_format!(LumaA, f32, to_luma_alpha32f);
@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+
+/// The errors that can occur while merging images.
+#[derive(Debug)]
+pub enum MergerError {
+    /// The image at `path` could not be read or decoded.
+    Decode {
+        path: PathBuf,
+        source: image::ImageError,
+    },
+    /// The in-memory image at `index` could not be decoded.
+    DecodeBuffer {
+        index: usize,
+        source: image::ImageError,
+    },
+    /// The given `index` is not a cell on the canvas, which only has `capacity` cells.
+    IndexOutOfBounds { index: u32, capacity: u32 },
+    /// More than one image was given for the cell at `index`.
+    DuplicateIndex { index: u32 },
+    /// An image was `got` pixels in size, but `expected` pixels were required. When the image was one of a batch, `index` is
+    /// its position in the batch.
+    DimensionMismatch {
+        index: Option<u32>,
+        expected: (u32, u32),
+        got: (u32, u32),
+    },
+    /// A container held `got` subpixels, but `needed` were required to hold the canvas.
+    ContainerTooSmall { needed: usize, got: usize },
+    /// A merger could not be created because its layout is invalid, for the given `reason`.
+    InvalidLayout { reason: &'static str },
+    /// An image of size `dimensions` placed at `at` would extend past the edge of a canvas of size `canvas`.
+    OutOfCanvas {
+        at: (u32, u32),
+        dimensions: (u32, u32),
+        canvas: (u32, u32),
+    },
+}
+
+impl std::fmt::Display for MergerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Decode { path, .. } => {
+                write!(f, "could not decode the image at {}", path.display())
+            }
+            Self::DecodeBuffer { index, .. } => {
+                write!(f, "could not decode the image at index {index}")
+            }
+            Self::IndexOutOfBounds { index, capacity } => write!(
+                f,
+                "index {index} is out of bounds for a canvas with {capacity} cells"
+            ),
+            Self::DuplicateIndex { index } => {
+                write!(f, "more than one image was given for index {index}")
+            }
+            Self::DimensionMismatch {
+                index,
+                expected,
+                got,
+            } => {
+                write!(
+                    f,
+                    "expected an image of {}x{} pixels, but got {}x{}",
+                    expected.0, expected.1, got.0, got.1
+                )?;
+                match index {
+                    Some(index) => write!(f, " at index {index}"),
+                    None => Ok(()),
+                }
+            }
+            Self::ContainerTooSmall { needed, got } => write!(
+                f,
+                "the container holds {got} subpixels, but {needed} are needed"
+            ),
+            Self::InvalidLayout { reason } => write!(f, "invalid layout: {reason}"),
+            Self::OutOfCanvas {
+                at,
+                dimensions,
+                canvas,
+            } => write!(
+                f,
+                "an image of {}x{} pixels at ({}, {}) extends past the edge of a {}x{} canvas",
+                dimensions.0, dimensions.1, at.0, at.1, canvas.0, canvas.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MergerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode { source, .. } | Self::DecodeBuffer { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -1,45 +1,726 @@
-use crate::{cell::ImageCell, core::Image, merger::Point, BufferedImage};
-use image::Pixel;
-use rayon::{
-    iter::IntoParallelIterator,
-    prelude::{IndexedParallelIterator, ParallelIterator},
-    slice::ParallelSlice,
+use crate::{
+    cell::ImageCell,
+    core::{AlphaPixel, Image},
+    merger::{ColorSpace, Point, Transform},
+    parallel::*,
+    BufferedImage, MergerError,
 };
-use std::{marker::Sync, ops::DerefMut};
+use image::{Luma, Pixel, Primitive};
+use num_traits::{NumCast, Zero};
+use std::{
+    marker::Sync,
+    ops::DerefMut,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// The number of pixels below which pasting an image is faster on the current thread than across the rayon thread pool.
+/// Mergers use this as the default cutoff for choosing between [paste](paste) and [paste_sequential](paste_sequential).
+pub const SEQUENTIAL_PASTE_THRESHOLD: u32 = 64 * 64;
+
+/// The fewest subpixels a single rayon task pastes at once. Narrow images are pasted several rows per task so the task
+/// overhead stays small compared to copying the rows.
+const PASTE_MIN_SUBPIXELS: usize = 16 * 1024;
+
+/// The fewest pixels a single rayon task resizes at once, which keeps the task overhead small compared to the work.
+const RESIZE_MIN_LEN: usize = 256;
 
 /// The library's underlying paste method. This is only used internally and should not be used by the user, but is exposed
 /// through the raw module for documentation purposes.
+///
+/// The image is pasted in parallel a whole row at a time, never a pixel at a time. Each rayon task copies at least one row,
+/// and at least 16K subpixels worth of rows for narrow images, so a single very large image is spread across every core
+/// without drowning in task overhead. A 4000x4000 RGBA image, for example, is split into at most 4000 tasks of one 16,000
+/// byte row each.
+/// # Arguments
+/// * `bottom` - The image to paste onto.
+/// * `top` - The image to paste.
+/// * `loc` - The location to paste the top image at.
+pub fn paste<P, Container, TopContainer>(
+    bottom: &ImageCell<P, image::ImageBuffer<P, Container>>,
+    top: &Image<P, image::ImageBuffer<P, TopContainer>>,
+    loc: Point,
+) where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+    Container: DerefMut<Target = [P::Subpixel]>,
+    TopContainer: DerefMut<Target = [P::Subpixel]>,
+{
+    // Go through each row of the image (at once), and copy it into its location on the canvas'
+    // underlying buffer. Rows are contiguous in both buffers, so this is a single memcpy per row.
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+    let row_len = top.width() as usize * channels;
+    if row_len == 0 {
+        return;
+    }
+
+    let canvas_width = bottom.width() as usize;
+    let pixels: &[P::Subpixel] = top;
+    let pixels = &pixels[..row_len * top.height() as usize];
+    pixels
+        .par_chunks_exact(row_len)
+        .with_min_len(PASTE_MIN_SUBPIXELS.div_ceil(row_len))
+        .enumerate()
+        .for_each(|(y, row)| copy_row(bottom, row, canvas_width, loc, y));
+}
+
+/// Copies a single row of a pasted image into the canvas, `y` rows below `loc`.
+#[inline(always)]
+fn copy_row<P, Container>(
+    bottom: &ImageCell<P, image::ImageBuffer<P, Container>>,
+    row: &[P::Subpixel],
+    canvas_width: usize,
+    loc: Point,
+    y: usize,
+) where
+    P: Pixel,
+    Container: DerefMut<Target = [P::Subpixel]>,
+{
+    let start = ((loc.y as usize + y) * canvas_width + loc.x as usize)
+        * <P as Pixel>::CHANNEL_COUNT as usize;
+
+    // Each row of the pasted image maps to a distinct span of the canvas, so no two rows write to the same place.
+    let canvas: &mut [P::Subpixel] = bottom.get_image_mut();
+    copy_subpixels(&mut canvas[start..start + row.len()], row);
+}
+
+/// Copies `src` into `dst`, which must be the same length. With the `simd` feature enabled, 8-bit subpixels are copied in
+/// explicit wide chunks. Everything else uses `copy_from_slice`.
+#[inline(always)]
+fn copy_subpixels<S: Primitive>(dst: &mut [S], src: &[S]) {
+    #[cfg(feature = "simd")]
+    if std::mem::size_of::<S>() == 1 {
+        // SAFETY: A single byte subpixel has no padding, so both slices can be viewed as plain bytes of the same length.
+        let (dst, src) = unsafe {
+            (
+                std::slice::from_raw_parts_mut(dst.as_mut_ptr() as *mut u8, dst.len()),
+                std::slice::from_raw_parts(src.as_ptr() as *const u8, src.len()),
+            )
+        };
+        return copy_bytes_simd(dst, src);
+    }
+
+    dst.copy_from_slice(src);
+}
+
+/// Copies `src` into `dst` 32 bytes at a time, finishing any remaining bytes with `copy_from_slice`.
+#[cfg(feature = "simd")]
+#[inline(always)]
+fn copy_bytes_simd(dst: &mut [u8], src: &[u8]) {
+    use std::simd::u8x32;
+
+    let mut dst_chunks = dst.chunks_exact_mut(u8x32::LEN);
+    let mut src_chunks = src.chunks_exact(u8x32::LEN);
+    for (dst, src) in (&mut dst_chunks).zip(&mut src_chunks) {
+        u8x32::from_slice(src).copy_to_slice(dst);
+    }
+    dst_chunks
+        .into_remainder()
+        .copy_from_slice(src_chunks.remainder());
+}
+
+/// Same as [paste](paste), but pastes the image on the current thread. For tiny images, this avoids the overhead of
+/// distributing the work across the rayon thread pool. The result is identical to [paste](paste).
 /// # Arguments
 /// * `bottom` - The image to paste onto.
 /// * `top` - The image to paste.
 /// * `loc` - The location to paste the top image at.
-pub fn paste<P, Container>(
+pub fn paste_sequential<P, Container, TopContainer>(
+    bottom: &ImageCell<P, image::ImageBuffer<P, Container>>,
+    top: &Image<P, image::ImageBuffer<P, TopContainer>>,
+    loc: Point,
+) where
+    P: Pixel,
+    Container: DerefMut<Target = [P::Subpixel]>,
+    TopContainer: DerefMut<Target = [P::Subpixel]>,
+{
+    let row_len = top.width() as usize * <P as Pixel>::CHANNEL_COUNT as usize;
+    if row_len == 0 {
+        return;
+    }
+
+    let canvas_width = bottom.width() as usize;
+    let pixels: &[P::Subpixel] = top;
+    let pixels = &pixels[..row_len * top.height() as usize];
+    pixels
+        .chunks_exact(row_len)
+        .enumerate()
+        .for_each(|(y, row)| copy_row(bottom, row, canvas_width, loc, y));
+}
+
+/// Same as [paste](paste), but the top image holds its channels in BGR or BGRA order, such as frames captured from a GPU.
+/// The first and third channels of each pixel are swapped as the rows are copied, so no reordered copy of the image is made.
+/// # Arguments
+/// * `bottom` - The image to paste onto.
+/// * `top` - The image to paste, in BGR or BGRA order.
+/// * `loc` - The location to paste the top image at.
+/// # Panics
+/// This function will panic if the pixel type has fewer than 3 channels.
+pub fn paste_bgr<P, Container, TopContainer>(
     bottom: &ImageCell<P, image::ImageBuffer<P, Container>>,
-    top: &Image<P, image::ImageBuffer<P, Container>>,
+    top: &Image<P, image::ImageBuffer<P, TopContainer>>,
     loc: Point,
 ) where
     P: Pixel + Sync,
     <P as Pixel>::Subpixel: Sync,
     Container: DerefMut<Target = [P::Subpixel]>,
+    TopContainer: DerefMut<Target = [P::Subpixel]>,
+{
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+    assert!(
+        channels >= 3,
+        "Only pixels with at least 3 channels can be reordered!"
+    );
+
+    let row_len = top.width() as usize * channels;
+    if row_len == 0 {
+        return;
+    }
+
+    let canvas_width = bottom.width() as usize;
+    let pixels: &[P::Subpixel] = top;
+    pixels[..row_len * top.height() as usize]
+        .par_chunks_exact(row_len)
+        .with_min_len(PASTE_MIN_SUBPIXELS.div_ceil(row_len))
+        .enumerate()
+        .for_each(|(y, row)| {
+            let start = ((loc.y as usize + y) * canvas_width + loc.x as usize) * channels;
+
+            // Each row of the pasted image maps to a distinct span of the canvas, so no two rows write to the same place.
+            let canvas: &mut [P::Subpixel] = bottom.get_image_mut();
+            let target = canvas[start..start + row_len].chunks_exact_mut(channels);
+            for (target, source) in target.zip(row.chunks_exact(channels)) {
+                target.copy_from_slice(source);
+                target.swap(0, 2);
+            }
+        });
+}
+
+/// Converts an sRGB encoded value, from 0.0 to 1.0, to linear light.
+#[inline(always)]
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a value in linear light, from 0.0 to 1.0, to sRGB encoding.
+#[inline(always)]
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Pastes an image with straight alpha onto the canvas, blending it over what is already there. Where the image is opaque
+/// it replaces the canvas, where it is transparent the canvas shows through, and in between the colors are mixed by the
+/// alpha of both images.
+/// # Arguments
+/// * `bottom` - The image to paste onto.
+/// * `top` - The image to blend onto it.
+/// * `loc` - The location to paste the top image at.
+/// * `color_space` - The color space to blend the colors in. Alpha is always blended as is.
+pub fn paste_blended<P, Container, TopContainer>(
+    bottom: &ImageCell<P, image::ImageBuffer<P, Container>>,
+    top: &Image<P, image::ImageBuffer<P, TopContainer>>,
+    loc: Point,
+    color_space: ColorSpace,
+) where
+    P: AlphaPixel + Sync,
+    Container: DerefMut<Target = [u8]>,
+    TopContainer: DerefMut<Target = [u8]>,
 {
-    // Go through each pixel in the image (at once), grab its relative location on the canvas,
-    // and alter the canvas underlying buffer to reflect the new pixel.
-    let image_width = top.width();
-    top.par_chunks_exact(<P as Pixel>::CHANNEL_COUNT as usize)
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+    let row_len = top.width() as usize * channels;
+    if row_len == 0 {
+        return;
+    }
+
+    // Every 8-bit value is decoded once up front rather than once per subpixel.
+    let decoded: [f32; 256] = std::array::from_fn(|value| match color_space {
+        ColorSpace::Srgb => value as f32 / 255.0,
+        ColorSpace::Linear => srgb_to_linear(value as f32 / 255.0),
+    });
+    let encode = |value: f32| {
+        let value = match color_space {
+            ColorSpace::Srgb => value,
+            ColorSpace::Linear => linear_to_srgb(value),
+        };
+        (value.clamp(0.0, 1.0) * 255.0).round() as u8
+    };
+
+    let canvas_width = bottom.width() as usize;
+    let pixels: &[u8] = top;
+    pixels[..row_len * top.height() as usize]
+        .par_chunks_exact(row_len)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let start = ((loc.y as usize + y) * canvas_width + loc.x as usize) * channels;
+
+            // Each row of the pasted image maps to a distinct span of the canvas, so no two rows write to the same place.
+            let canvas: &mut [u8] = bottom.get_image_mut();
+            let target = canvas[start..start + row_len].chunks_exact_mut(channels);
+            for (target, source) in target.zip(row.chunks_exact(channels)) {
+                let source_alpha = source[channels - 1] as f32 / 255.0;
+                let target_alpha = target[channels - 1] as f32 / 255.0;
+                let alpha = source_alpha + target_alpha * (1.0 - source_alpha);
+                if alpha == 0.0 {
+                    target.fill(0);
+                    continue;
+                }
+
+                for (to, &from) in target[..channels - 1].iter_mut().zip(source) {
+                    let color = decoded[from as usize] * source_alpha
+                        + decoded[*to as usize] * target_alpha * (1.0 - source_alpha);
+                    *to = encode(color / alpha);
+                }
+                target[channels - 1] = (alpha * 255.0).round() as u8;
+            }
+        });
+}
+
+/// Returns whether every pixel of an image is fully transparent, stopping at the first pixel that is not. Blending such an
+/// image onto a canvas with [paste_blended](paste_blended) leaves the canvas unchanged.
+/// # Arguments
+/// * `image` - The image to check.
+pub fn is_fully_transparent<P, Container>(
+    image: &Image<P, image::ImageBuffer<P, Container>>,
+) -> bool
+where
+    P: AlphaPixel,
+    Container: DerefMut<Target = [u8]>,
+{
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+    let pixels: &[u8] = image;
+    pixels
+        .chunks_exact(channels)
+        .all(|pixel| pixel[channels - 1] == 0)
+}
+
+/// Same as [paste](paste), but only pastes a rectangular region of the top image. This avoids cropping the region into its
+/// own image first, as each row is copied straight from the top image into the canvas.
+/// # Arguments
+/// * `bottom` - The image to paste onto.
+/// * `top` - The image to paste a region of.
+/// * `src` - The top left corner of the region on the top image.
+/// * `dimensions` - The width and height of the region.
+/// * `loc` - The location to paste the region at.
+/// # Panics
+/// This function will panic if the region extends past the edge of the top image.
+pub fn paste_region<P, Container, TopContainer>(
+    bottom: &ImageCell<P, image::ImageBuffer<P, Container>>,
+    top: &Image<P, image::ImageBuffer<P, TopContainer>>,
+    src: Point,
+    dimensions: (u32, u32),
+    loc: Point,
+) where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+    Container: DerefMut<Target = [P::Subpixel]>,
+    TopContainer: DerefMut<Target = [P::Subpixel]>,
+{
+    let (width, height) = dimensions;
+    assert!(
+        src.x + width <= top.width() && src.y + height <= top.height(),
+        "The region to paste extends past the edge of the image!"
+    );
+
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+    let row_len = width as usize * channels;
+    if row_len == 0 || height == 0 {
+        return;
+    }
+
+    // Skip the rows above the region, then copy only the span of each remaining row that lies within it.
+    let top_row_len = top.width() as usize * channels;
+    let offset = src.x as usize * channels;
+    let canvas_width = bottom.width() as usize;
+    let pixels: &[P::Subpixel] = top;
+    pixels[src.y as usize * top_row_len..(src.y + height) as usize * top_row_len]
+        .par_chunks_exact(top_row_len)
+        .enumerate()
+        .for_each(|(y, row)| {
+            copy_row(bottom, &row[offset..offset + row_len], canvas_width, loc, y)
+        });
+}
+
+/// What [paste_checked](paste_checked) does with an image that would extend past the edge of the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Paste nothing and return an error.
+    Error,
+    /// Paste only the part of the image that lies on the canvas.
+    Clip,
+}
+
+/// Same as [paste](paste), but checks that the image lies on the canvas first. This makes it safe to paste at arbitrary
+/// locations, such as the edge tiles of layouts that are not a grid.
+/// # Arguments
+/// * `bottom` - The image to paste onto.
+/// * `top` - The image to paste.
+/// * `loc` - The location to paste the top image at.
+/// * `policy` - What to do if the image would extend past the edge of the canvas.
+/// # Returns
+/// * `Ok` - If the image, or the part of it on the canvas when clipping, was pasted.
+/// * `Err(MergerError::OutOfCanvas)` - If the image would extend past the edge of the canvas and the policy is
+///   `OverflowPolicy::Error`. Nothing is pasted in this case.
+pub fn paste_checked<P, Container, TopContainer>(
+    bottom: &ImageCell<P, image::ImageBuffer<P, Container>>,
+    top: &Image<P, image::ImageBuffer<P, TopContainer>>,
+    loc: Point,
+    policy: OverflowPolicy,
+) -> Result<(), MergerError>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+    Container: DerefMut<Target = [P::Subpixel]>,
+    TopContainer: DerefMut<Target = [P::Subpixel]>,
+{
+    let (canvas_width, canvas_height) = bottom.dimensions();
+    let fits_x = loc
+        .x
+        .checked_add(top.width())
+        .is_some_and(|end| end <= canvas_width);
+    let fits_y = loc
+        .y
+        .checked_add(top.height())
+        .is_some_and(|end| end <= canvas_height);
+    if fits_x && fits_y {
+        paste(bottom, top, loc);
+        return Ok(());
+    }
+
+    if policy == OverflowPolicy::Error {
+        return Err(MergerError::OutOfCanvas {
+            at: (loc.x, loc.y),
+            dimensions: top.dimensions(),
+            canvas: (canvas_width, canvas_height),
+        });
+    }
+
+    // Only copy the start of each row, and the first rows, that land on the canvas.
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+    let width = canvas_width.saturating_sub(loc.x).min(top.width()) as usize;
+    let height = canvas_height.saturating_sub(loc.y).min(top.height()) as usize;
+    let row_len = width * channels;
+    if row_len == 0 || height == 0 {
+        return Ok(());
+    }
+
+    let top_row_len = top.width() as usize * channels;
+    let pixels: &[P::Subpixel] = top;
+    pixels[..top_row_len * height]
+        .par_chunks_exact(top_row_len)
+        .enumerate()
+        .for_each(|(y, row)| copy_row(bottom, &row[..row_len], canvas_width as usize, loc, y));
+
+    Ok(())
+}
+
+/// Linearly interpolates from one subpixel to another by `amount`, where 0.0 is `from` and 1.0 is `to`.
+#[inline(always)]
+fn lerp<S: Primitive>(from: S, to: S, amount: f32) -> S {
+    let from: f32 = NumCast::from(from).unwrap();
+    let to: f32 = NumCast::from(to).unwrap();
+    let value = from + (to - from) * amount;
+
+    // Floating point subpixels have a maximum value of 1.0 and must not be rounded.
+    let max: f32 = NumCast::from(S::DEFAULT_MAX_VALUE).unwrap();
+    let value = if max > 1.0 { value.round() } else { value };
+
+    NumCast::from(value).unwrap()
+}
+
+/// Pastes an image onto the canvas through a mask. Each mask value controls how much of the image's pixel replaces the
+/// canvas' pixel, where 0 keeps the canvas' pixel and 255 fully replaces it. The mask must be the same size as the image.
+/// # Arguments
+/// * `bottom` - The image to paste onto.
+/// * `top` - The image to paste.
+/// * `mask` - The mask to paste the image through.
+/// * `loc` - The location to paste the top image at.
+pub fn paste_masked<P, Container, TopContainer, MaskContainer>(
+    bottom: &ImageCell<P, image::ImageBuffer<P, Container>>,
+    top: &Image<P, image::ImageBuffer<P, TopContainer>>,
+    mask: &Image<Luma<u8>, image::ImageBuffer<Luma<u8>, MaskContainer>>,
+    loc: Point,
+) where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+    Container: DerefMut<Target = [P::Subpixel]>,
+    TopContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    MaskContainer: DerefMut<Target = [u8]> + Sync,
+{
+    (0..top.height()).into_par_iter().for_each(|y| {
+        // Each row of the pasted image maps to a distinct span of the canvas, so no two rows write to the same place.
+        let canvas = bottom.get_image_mut();
+
+        for x in 0..top.width() {
+            let amount = mask.get_pixel(x, y).0[0] as f32 / u8::MAX as f32;
+            let source = top.get_pixel(x, y);
+            let target = canvas.get_pixel_mut(loc.x + x, loc.y + y);
+
+            for (subpixel, source) in target.channels_mut().iter_mut().zip(source.channels()) {
+                *subpixel = lerp(*subpixel, *source, amount);
+            }
+        }
+    });
+}
+
+/// Copies a rectangular region out of an image, one row at a time in parallel.
+/// # Arguments
+/// * `image` - The image to copy the region from.
+/// * `loc` - The top left corner of the region.
+/// * `dimensions` - The width and height of the region.
+/// # Returns
+/// * A new, `Vec` based image holding the copied region.
+/// # Panics
+/// This function will panic if the region extends past the edge of the image.
+pub fn crop<P, Container>(
+    image: &Image<P, image::ImageBuffer<P, Container>>,
+    loc: Point,
+    dimensions: (u32, u32),
+) -> BufferedImage<P>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync + Send,
+    Container: DerefMut<Target = [P::Subpixel]>,
+{
+    let (width, height) = dimensions;
+    assert!(
+        loc.x + width <= image.width() && loc.y + height <= image.height(),
+        "The region to crop extends past the edge of the image!"
+    );
+
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+    let row_len = width as usize * channels;
+    let mut cropped: BufferedImage<P> = Image::new(width, height);
+    if row_len == 0 {
+        return cropped;
+    }
+
+    let image_width = image.width() as usize;
+    let pixels: &[P::Subpixel] = image;
+    cropped
+        .par_chunks_exact_mut(row_len)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let start = ((loc.y as usize + y) * image_width + loc.x as usize) * channels;
+            row.copy_from_slice(&pixels[start..start + row_len]);
+        });
+
+    cropped
+}
+
+/// Fills a rectangular region of an image with a single pixel, one row at a time in parallel. The region is clipped to the
+/// edges of the image, so any part of it outside the image is ignored.
+/// # Arguments
+/// * `image` - The image to fill the region of.
+/// * `loc` - The top left corner of the region.
+/// * `dimensions` - The width and height of the region.
+/// * `fill` - The pixel to fill the region with.
+pub fn fill_rect<P, Container>(
+    image: &mut Image<P, image::ImageBuffer<P, Container>>,
+    loc: Point,
+    dimensions: (u32, u32),
+    fill: P,
+) where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync + Send,
+    Container: DerefMut<Target = [P::Subpixel]>,
+{
+    let (image_width, image_height) = image.dimensions();
+    let x_end = loc.x.saturating_add(dimensions.0).min(image_width) as usize;
+    let y_end = loc.y.saturating_add(dimensions.1).min(image_height) as usize;
+    let (x, y) = (loc.x as usize, loc.y as usize);
+    if x >= x_end || y >= y_end {
+        return;
+    }
+
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+    let row_len = image_width as usize * channels;
+    let fill = fill.channels();
+    let pixels: &mut [P::Subpixel] = image;
+    pixels[y * row_len..y_end * row_len]
+        .par_chunks_exact_mut(row_len)
+        .for_each(|row| {
+            row[x * channels..x_end * channels]
+                .chunks_exact_mut(channels)
+                .for_each(|pixel| pixel.copy_from_slice(fill));
+        });
+}
+
+/// Fades every subpixel of an image by the given opacity. With no tint, each subpixel is scaled by `opacity`, fading the
+/// image towards zero. With a tint, each subpixel is instead linearly interpolated from the tint's subpixel towards the
+/// image's subpixel by `opacity`, fading the image towards the tint.
+/// # Arguments
+/// * `image` - The image to fade.
+/// * `opacity` - How much of the original image to keep, from 0.0 to 1.0.
+/// * `tint` - The color to fade the image towards, or None to fade towards zero.
+/// # Returns
+/// * A new, `Vec` based image with the faded pixels.
+pub fn tint<P, U>(image: &Image<P, U>, opacity: f32, tint: Option<P>) -> BufferedImage<P>
+where
+    P: Pixel + Sync + Send,
+    <P as Pixel>::Subpixel: Sync + Send,
+    U: image::GenericImage<Pixel = P> + Sync,
+{
+    let opacity = opacity.clamp(0.0, 1.0);
+    let zero: <P as Pixel>::Subpixel = Zero::zero();
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+
+    let mut tinted: BufferedImage<P> = Image::new(image.width(), image.height());
+    tinted
+        .par_chunks_exact_mut(channels)
         .enumerate()
         .for_each(|(index, chunk)| {
-            let x = index as u32 % image_width;
-            let y = index as u32 / image_width;
+            let x = index as u32 % image.width();
+            let y = index as u32 / image.width();
+            let source = image.get_pixel(x, y);
 
-            let canvas_x = loc.x + x;
-            let canvas_y = loc.y + y;
+            for (channel, subpixel) in chunk.iter_mut().enumerate() {
+                let target = tint.map_or(zero, |tint| tint.channels()[channel]);
+                *subpixel = lerp(target, source.channels()[channel], opacity);
+            }
+        });
 
-            let pixel = <P as Pixel>::from_slice(chunk);
-            unsafe {
-                let mut handout = bottom.request_handout(canvas_x, canvas_y);
-                handout.unsafe_put_pixel(*pixel);
+    tinted
+}
+
+/// Applies an orientation transform to an image, remapping its pixels in parallel.
+/// # Arguments
+/// * `image` - The image to transform.
+/// * `transform` - The transform to apply. Rotations swap the width and height of the image.
+/// # Returns
+/// * A new, `Vec` based image with the transformed pixels.
+pub fn transform<P, U>(image: &Image<P, U>, transform: Transform) -> BufferedImage<P>
+where
+    P: Pixel + Sync + Send,
+    <P as Pixel>::Subpixel: Sync + Send,
+    U: image::GenericImage<Pixel = P> + Sync,
+{
+    let (width, height) = image.dimensions();
+    let (nwidth, nheight) = transform.transformed_dimensions((width, height));
+
+    let mut transformed: BufferedImage<P> = Image::new(nwidth, nheight);
+    transformed
+        .par_chunks_exact_mut(<P as Pixel>::CHANNEL_COUNT as usize)
+        .enumerate()
+        .for_each(|(index, chunk)| {
+            let i = index as u32 % nwidth;
+            let j = index as u32 / nwidth;
+
+            // Find the source pixel that lands at (i, j) after the transform.
+            let (x, y) = match transform {
+                Transform::None => (i, j),
+                Transform::FlipH => (width - 1 - i, j),
+                Transform::FlipV => (i, height - 1 - j),
+                Transform::Rotate90 => (j, height - 1 - i),
+                Transform::Rotate180 => (width - 1 - i, height - 1 - j),
+                Transform::Rotate270 => (width - 1 - j, i),
+            };
+
+            chunk.copy_from_slice(image.get_pixel(x, y).channels());
+        });
+
+    transformed
+}
+
+/// Converts an image to single channel luminance in parallel, using the Rec. 601 weights of 0.299 red, 0.587 green, and
+/// 0.114 blue. Grayscale images keep their values, and any alpha channel is dropped.
+/// # Arguments
+/// * `image` - The image to convert.
+/// # Returns
+/// * A new, `Vec` based `Luma<u8>` image with the same dimensions as the input image.
+pub fn to_luma<P, U>(image: &Image<P, U>) -> BufferedImage<Luma<u8>>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+    U: image::GenericImage<Pixel = P> + Sync,
+{
+    let max: f32 = NumCast::from(<P::Subpixel as Primitive>::DEFAULT_MAX_VALUE).unwrap_or(1.0);
+    let width = image.width();
+
+    let mut luma: BufferedImage<Luma<u8>> = Image::new(width, image.height());
+    luma.par_iter_mut()
+        .enumerate()
+        .for_each(|(index, subpixel)| {
+            let x = index as u32 % width;
+            let y = index as u32 / width;
+            let [r, g, b] = image
+                .get_pixel(x, y)
+                .to_rgb()
+                .0
+                .map(|channel| NumCast::from(channel).unwrap_or(0.0f32) / max);
+
+            let value = 0.299 * r + 0.587 * g + 0.114 * b;
+            *subpixel = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        });
+
+    luma
+}
+
+/// Builds a new image by applying `convert` to every color channel of an image, along with the pixel's alpha. The alpha
+/// channel itself is kept as is.
+fn map_colors<P, U>(image: &Image<P, U>, convert: impl Fn(u8, u8) -> u8 + Sync) -> BufferedImage<P>
+where
+    P: AlphaPixel + Sync,
+    U: image::GenericImage<Pixel = P> + Sync,
+{
+    let width = image.width();
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+
+    let mut converted: BufferedImage<P> = Image::new(width, image.height());
+    converted
+        .par_chunks_exact_mut(channels)
+        .enumerate()
+        .for_each(|(index, pixel)| {
+            let source = image.get_pixel(index as u32 % width, index as u32 / width);
+            let source = source.channels();
+            let alpha = source[channels - 1];
+
+            for (to, &from) in pixel.iter_mut().zip(&source[..channels - 1]) {
+                *to = convert(from, alpha);
             }
+            pixel[channels - 1] = alpha;
         });
+
+    converted
+}
+
+/// Converts an image with straight alpha to premultiplied alpha in parallel, multiplying each color channel by
+/// `alpha / 255` and rounding to the nearest value.
+/// # Arguments
+/// * `image` - The image to convert.
+/// # Returns
+/// * A new, `Vec` based image with premultiplied alpha.
+pub fn premultiply<P, U>(image: &Image<P, U>) -> BufferedImage<P>
+where
+    P: AlphaPixel + Sync,
+    U: image::GenericImage<Pixel = P> + Sync,
+{
+    map_colors(image, |color, alpha| {
+        ((color as u32 * alpha as u32 + 127) / 255) as u8
+    })
+}
+
+/// Converts an image with premultiplied alpha back to straight alpha in parallel, reversing [premultiply](premultiply).
+/// Fully transparent pixels become transparent black, as their color can not be recovered.
+/// # Arguments
+/// * `image` - The image to convert.
+/// # Returns
+/// * A new, `Vec` based image with straight alpha.
+pub fn unpremultiply<P, U>(image: &Image<P, U>) -> BufferedImage<P>
+where
+    P: AlphaPixel + Sync,
+    U: image::GenericImage<Pixel = P> + Sync,
+{
+    map_colors(image, |color, alpha| match alpha {
+        0 => 0,
+        alpha => ((color as u32 * 255 + alpha as u32 / 2) / alpha as u32).min(255) as u8,
+    })
 }
 
 /// The library's underlying resize method. This is only used internally and should not be used by the user, but is exposed
@@ -64,14 +745,40 @@ where
     let new_image: Image<P, _> = Image::new(nwidth, nheight);
     let cell = ImageCell::new(new_image);
 
-    // Grab the ratios of the new image to the old image.
-    let height_ratio = image.height() as f32 / nheight as f32;
-    let width_ratio = image.width() as f32 / nwidth as f32;
+    // Grab the ratios of the new image to the old image. These are f64 as f32 can not represent every coordinate of very
+    // large images, which would misalign the sampled pixels towards the far edge.
+    let height_ratio = image.height() as f64 / nheight as f64;
+    let width_ratio = image.width() as f64 / nwidth as f64;
+
+    // Sample the source pixel whose center is nearest to the center of the target pixel, matching the
+    // reference implementation in `image::imageops::resize`.
+    let source_coordinate = |target: u32, ratio: f64, max: u32| -> u32 {
+        let source = ((target as f64 + 0.5) * ratio - 0.5).round();
+        source.clamp(0.0, max.saturating_sub(1) as f64) as u32
+    };
+
+    // The handouts below are only sound if every target pixel is written exactly once. Debug builds track each write to
+    // verify this.
+    let total = nwidth as usize * nheight as usize;
+    let visited: Option<Vec<AtomicBool>> =
+        cfg!(debug_assertions).then(|| (0..total).map(|_| AtomicBool::new(false)).collect());
+
+    // Iterate over every target pixel at once rather than nesting the loops, so that images with skewed aspect ratios
+    // are split evenly across the thread pool.
+    (0..total)
+        .into_par_iter()
+        .with_min_len(RESIZE_MIN_LEN)
+        .for_each(|index| {
+            if let Some(visited) = &visited {
+                let written = visited[index].swap(true, Ordering::Relaxed);
+                debug_assert!(!written, "Target pixel {index} was written more than once!");
+            }
 
-    (0..nwidth).into_par_iter().for_each(|i| {
-        (0..nheight).into_par_iter().for_each(|j| {
-            let x = (i as f32 * width_ratio) as u32;
-            let y = (j as f32 * height_ratio) as u32;
+            let i = (index % nwidth as usize) as u32;
+            let j = (index / nwidth as usize) as u32;
+
+            let x = source_coordinate(i, width_ratio, image.width());
+            let y = source_coordinate(j, height_ratio, image.height());
 
             let pixel = image.get_pixel(x, y);
 
@@ -79,16 +786,201 @@ where
                 let mut handout = cell.request_handout(i, j);
                 handout.unsafe_put_pixel(pixel)
             }
-        })
-    });
+        });
+
+    if let Some(visited) = &visited {
+        debug_assert!(
+            visited.iter().all(|pixel| pixel.load(Ordering::Relaxed)),
+            "Not every target pixel was written!"
+        );
+    }
 
     cell.into_inner()
 }
 
+/// Scales an image up by a whole number, replicating each source pixel into a `factor` by `factor` block. Only integer
+/// indexing is used, so unlike [resize_nearest_neighbor](resize_nearest_neighbor) there is no rounding that could misplace
+/// a pixel, which keeps pixel art crisp. Rows are filled in parallel.
+/// # Arguments
+/// * `image` - The image to scale.
+/// * `factor` - How many times larger the scaled image is along each axis.
+/// # Returns
+/// * A new, `Vec` based image that is `factor` times the width and height of the input image.
+/// # Panics
+/// This function will panic if `factor` is 0, or the scaled image would be larger than `u32::MAX` pixels along either axis.
+pub fn resize_integer<P, U>(image: &Image<P, U>, factor: u32) -> BufferedImage<P>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync + Send,
+    U: image::GenericImage<Pixel = P> + Sync,
+{
+    assert!(factor > 0, "An image can not be scaled by a factor of 0!");
+    let (width, height) = image.dimensions();
+    let scaled_width = width
+        .checked_mul(factor)
+        .expect("The scaled image is too wide!");
+    let scaled_height = height
+        .checked_mul(factor)
+        .expect("The scaled image is too tall!");
+
+    let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+    let row_len = scaled_width as usize * channels;
+    let mut scaled: BufferedImage<P> = Image::new(scaled_width, scaled_height);
+    if row_len == 0 {
+        return scaled;
+    }
+
+    scaled
+        .par_chunks_exact_mut(row_len)
+        .enumerate()
+        .for_each(|(j, row)| {
+            let y = j as u32 / factor;
+            for (x, block) in row.chunks_exact_mut(channels * factor as usize).enumerate() {
+                let pixel = image.get_pixel(x as u32, y);
+                for target in block.chunks_exact_mut(channels) {
+                    target.copy_from_slice(pixel.channels());
+                }
+            }
+        });
+
+    scaled
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use image::Rgba;
+    use image::{Rgb, Rgba};
+
+    /// Pastes a 4x2 tile of increasing values onto a blank 6x3 canvas at (4, 1) with the given policy.
+    fn paste_off_right_edge(
+        policy: OverflowPolicy,
+    ) -> (Result<(), MergerError>, BufferedImage<Luma<u8>>) {
+        let tile: Image<Luma<u8>, _> = Image::from(image::ImageBuffer::from_fn(4, 2, |x, y| {
+            Luma([(1 + x + y * 4) as u8])
+        }));
+        let canvas = ImageCell::new(Image::<Luma<u8>, _>::new(6, 3));
+        let result = paste_checked(&canvas, &tile, Point { x: 4, y: 1 }, policy);
+
+        (result, canvas.into_inner())
+    }
+
+    #[test]
+    fn test_paste_checked_error() {
+        let (result, canvas) = paste_off_right_edge(OverflowPolicy::Error);
+        assert!(matches!(
+            result,
+            Err(MergerError::OutOfCanvas {
+                at: (4, 1),
+                dimensions: (4, 2),
+                canvas: (6, 3)
+            })
+        ));
+        assert!(canvas.pixels().all(|pixel| pixel[0] == 0));
+    }
+
+    #[test]
+    fn test_paste_checked_clip() {
+        let (result, canvas) = paste_off_right_edge(OverflowPolicy::Clip);
+        assert!(result.is_ok());
+
+        let pixels: &[u8] = &canvas;
+        #[rustfmt::skip]
+        assert_eq!(pixels, &[
+            0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 1, 2,
+            0, 0, 0, 0, 5, 6,
+        ]);
+    }
+
+    #[test]
+    fn test_paste_bgr() {
+        let top: Image<Rgb<u8>, _> = Image::from(image::ImageBuffer::from_fn(2, 2, |x, y| {
+            Rgb([x as u8, y as u8, 200])
+        }));
+        let canvas = ImageCell::new(Image::<Rgb<u8>, _>::new(3, 2));
+        paste_bgr(&canvas, &top, Point { x: 1, y: 0 });
+
+        let canvas = canvas.into_inner();
+        assert_eq!(*canvas.get_pixel(0, 0), Rgb([0, 0, 0]));
+        assert_eq!(*canvas.get_pixel(1, 0), Rgb([200, 0, 0]));
+        assert_eq!(*canvas.get_pixel(2, 1), Rgb([200, 1, 1]));
+    }
+
+    /// Blends a half transparent red pixel over an opaque green one in the given color space.
+    fn blend_red_over_green(color_space: ColorSpace) -> Rgba<u8> {
+        let top: Image<Rgba<u8>, _> = Image::new_from_pixel(1, 1, Rgba([255, 0, 0, 128]));
+        let canvas = ImageCell::new(Image::new_from_pixel(1, 1, Rgba([0u8, 255, 0, 255])));
+        paste_blended(&canvas, &top, Point { x: 0, y: 0 }, color_space);
+
+        *canvas.into_inner().get_pixel(0, 0)
+    }
+
+    #[test]
+    fn test_paste_blended() {
+        let srgb = blend_red_over_green(ColorSpace::Srgb);
+        let linear = blend_red_over_green(ColorSpace::Linear);
+        assert_eq!(srgb, Rgba([128, 127, 0, 255]));
+
+        // Mixing in linear light keeps both colors brighter than mixing their sRGB values.
+        assert_eq!(linear, Rgba([188, 187, 0, 255]));
+        assert!(linear[0] > srgb[0] && linear[1] > srgb[1]);
+
+        // Blending onto a fully transparent canvas leaves the image as is.
+        let top: Image<Rgba<u8>, _> = Image::new_from_pixel(1, 1, Rgba([10, 20, 30, 40]));
+        let canvas = ImageCell::new(Image::<Rgba<u8>, _>::new(1, 1));
+        paste_blended(&canvas, &top, Point { x: 0, y: 0 }, ColorSpace::Linear);
+        assert_eq!(*canvas.into_inner().get_pixel(0, 0), Rgba([10, 20, 30, 40]));
+    }
+
+    #[test]
+    fn test_paste_region() {
+        let tile: Image<Luma<u8>, _> = Image::from(image::ImageBuffer::from_fn(4, 3, |x, y| {
+            Luma([(1 + x + y * 4) as u8])
+        }));
+        let canvas = ImageCell::new(Image::<Luma<u8>, _>::new(4, 3));
+        paste_region(
+            &canvas,
+            &tile,
+            Point { x: 1, y: 1 },
+            (2, 2),
+            Point { x: 2, y: 0 },
+        );
+
+        let canvas = canvas.into_inner();
+        let pixels: &[u8] = &canvas;
+        #[rustfmt::skip]
+        assert_eq!(pixels, &[
+            0, 0, 6, 7,
+            0, 0, 10, 11,
+            0, 0, 0, 0,
+        ]);
+    }
+
+    #[test]
+    fn test_paste_matches_scalar_copy() {
+        // Odd offsets and widths make sure the wide copies of 8-bit subpixels handle unaligned rows and leftover bytes.
+        let mut tile: Image<Rgba<u8>, _> = Image::new(128, 128);
+        for (x, y, pixel) in tile.enumerate_pixels_mut() {
+            *pixel = Rgba([x as u8, y as u8, (x * y) as u8, (x ^ y) as u8]);
+        }
+        let loc = Point { x: 3, y: 5 };
+
+        let canvas = ImageCell::new(Image::<Rgba<u8>, _>::new(135, 140));
+        paste(&canvas, &tile, loc);
+
+        let mut expected: Image<Rgba<u8>, _> = Image::new(135, 140);
+        let row_len = 128 * 4;
+        let canvas_row_len = 135 * 4;
+        let expected_pixels: &mut [u8] = &mut expected;
+        for (y, row) in tile.chunks_exact(row_len).enumerate() {
+            let start = (loc.y as usize + y) * canvas_row_len + loc.x as usize * 4;
+            expected_pixels[start..start + row_len].copy_from_slice(row);
+        }
+
+        let pasted = canvas.into_inner();
+        let pasted_pixels: &[u8] = &pasted;
+        assert_eq!(pasted_pixels, expected_pixels);
+    }
 
     #[test]
     fn test_resize_nearest_neighbor() {
@@ -112,4 +1004,147 @@ mod tests {
 
         assert_eq!(fast_resized_underlying, slow_resized);
     }
+
+    #[test]
+    fn test_resize_nearest_neighbor_gradient() {
+        let mut image: Image<Rgba<u8>, _> = Image::new(100, 100);
+        for (x, y, pixel) in image.enumerate_pixels_mut() {
+            *pixel = Rgba([x as u8 * 2, y as u8 * 2, (x + y) as u8, 255]);
+        }
+
+        let fast_resized = resize_nearest_neighbor(&image, 30, 30).into_buffer();
+        let slow_resized =
+            image::imageops::resize(&*image, 30, 30, image::imageops::FilterType::Nearest);
+
+        assert_eq!(fast_resized, slow_resized);
+    }
+
+    #[test]
+    fn test_resize_writes_every_pixel_once() {
+        // Debug builds verify that every target pixel is written exactly once, so this resize exercises that check for
+        // both shrinking and growing along each axis.
+        let image: Image<Rgba<u8>, _> = Image::new(7, 3);
+        for (nwidth, nheight) in [(3, 5), (11, 2), (1, 1)] {
+            let resized = resize_nearest_neighbor(&image, nwidth, nheight);
+            assert_eq!(resized.dimensions(), (nwidth, nheight));
+        }
+    }
+
+    #[test]
+    fn test_resize_nearest_neighbor_wide() {
+        // Each pixel encodes its own column, so the resized image shows which source column was sampled.
+        let width = 20_000;
+        let mut image: Image<Rgba<u8>, _> = Image::new(width, 2);
+        for (x, _, pixel) in image.enumerate_pixels_mut() {
+            *pixel = Rgba([(x % 256) as u8, (x / 256) as u8, 0, 255]);
+        }
+        let column = |pixel: &Rgba<u8>| pixel[0] as u32 + pixel[1] as u32 * 256;
+
+        let nwidth = 19_999;
+        let resized = resize_nearest_neighbor(&image, nwidth, 1);
+
+        // The center of target column i is at (i + 0.5) * width / nwidth in the source, so the nearest source column is
+        // round(((2i + 1) * width - nwidth) / (2 * nwidth)), computed here exactly with integers.
+        let expected = |i: u32| {
+            let numerator = (2 * i as u64 + 1) * width as u64 - nwidth as u64;
+            ((numerator + nwidth as u64) / (2 * nwidth as u64)) as u32
+        };
+        for i in [0, nwidth / 2, nwidth - 2, nwidth - 1] {
+            assert_eq!(column(resized.get_pixel(i, 0)), expected(i));
+        }
+        assert_eq!(column(resized.get_pixel(nwidth - 1, 0)), width - 1);
+    }
+
+    #[test]
+    fn test_resize_nearest_neighbor_non_square() {
+        let mut image: Image<Rgba<u8>, _> = Image::new(500, 10);
+        for (x, y, pixel) in image.enumerate_pixels_mut() {
+            *pixel = Rgba([(x % 256) as u8, (x / 256) as u8, y as u8 * 20, 255]);
+        }
+
+        let fast_resized = resize_nearest_neighbor(&image, 50, 5).into_buffer();
+        let slow_resized =
+            image::imageops::resize(&*image, 50, 5, image::imageops::FilterType::Nearest);
+
+        assert_eq!(fast_resized, slow_resized);
+    }
+
+    #[test]
+    fn test_resize_integer() {
+        let tile: Image<Rgb<u8>, _> = Image::from(image::ImageBuffer::from_fn(3, 3, |x, y| {
+            Rgb([x as u8 * 10, y as u8 * 10, (x + y * 3) as u8])
+        }));
+
+        let scaled = resize_integer(&tile, 2);
+        assert_eq!(scaled.dimensions(), (6, 6));
+        for (x, y, pixel) in scaled.enumerate_pixels() {
+            assert_eq!(pixel, tile.get_pixel(x / 2, y / 2), "({x}, {y})");
+        }
+
+        assert_eq!(resize_integer(&tile, 1), tile);
+    }
+
+    #[test]
+    fn test_paste_sequential_matches_paste() {
+        for size in [1, 4] {
+            let mut top: BufferedImage<Rgba<u8>> = Image::new(size, size);
+            for (x, y, pixel) in top.enumerate_pixels_mut() {
+                *pixel = Rgba([x as u8, y as u8, (x * y) as u8, 255]);
+            }
+
+            let parallel = ImageCell::new(BufferedImage::<Rgba<u8>>::new(10, 10));
+            let sequential = ImageCell::new(BufferedImage::<Rgba<u8>>::new(10, 10));
+
+            paste(&parallel, &top, Point { x: 3, y: 2 });
+            paste_sequential(&sequential, &top, Point { x: 3, y: 2 });
+
+            assert_eq!(parallel.into_inner(), sequential.into_inner());
+        }
+    }
+
+    #[test]
+    fn test_transform() {
+        let mut image: BufferedImage<Rgba<u8>> = Image::new(7, 4);
+        for (x, y, pixel) in image.enumerate_pixels_mut() {
+            *pixel = Rgba([x as u8, y as u8, (x * 10 + y) as u8, 255]);
+        }
+
+        let underlying = &*image;
+        let cases = [
+            (Transform::None, underlying.clone()),
+            (
+                Transform::FlipH,
+                image::imageops::flip_horizontal(underlying),
+            ),
+            (Transform::FlipV, image::imageops::flip_vertical(underlying)),
+            (Transform::Rotate90, image::imageops::rotate90(underlying)),
+            (Transform::Rotate180, image::imageops::rotate180(underlying)),
+            (Transform::Rotate270, image::imageops::rotate270(underlying)),
+        ];
+
+        for (variant, expected) in cases {
+            assert_eq!(
+                transform(&image, variant).into_buffer(),
+                expected,
+                "{:?}",
+                variant
+            );
+        }
+    }
+
+    #[test]
+    fn test_paste_large_tile() {
+        let mut top: BufferedImage<Rgba<u8>> = Image::new(256, 256);
+        for (x, y, pixel) in top.enumerate_pixels_mut() {
+            *pixel = Rgba([x as u8, y as u8, (x ^ y) as u8, 255]);
+        }
+
+        let bottom = ImageCell::new(BufferedImage::<Rgba<u8>>::new(300, 280));
+        paste(&bottom, &top, Point { x: 30, y: 17 });
+
+        let mut expected = image::RgbaImage::new(300, 280);
+        image::imageops::overlay(&mut expected, &*top, 30, 17);
+
+        assert_eq!(bottom.into_inner().into_buffer(), expected);
+    }
 }
@@ -3,12 +3,16 @@
 //! avoiding unnecessary costly operations.
 //!
 //! The main type of this crate is the [KnownSizeMerger](crate::KnownSizeMerger) struct, but, more will be added in the future.
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 mod cell;
 mod core;
+mod error;
 mod functions;
 mod merger;
+mod parallel;
 
 pub use crate::core::*;
+pub use crate::error::*;
 pub use crate::merger::*;
 pub use image::{ImageBuffer, Luma, LumaA, Pixel, Rgb, Rgba};
 
@@ -0,0 +1,217 @@
+use super::core::Merger;
+use crate::{parallel::*, BufferedImage, Image, MergerError};
+
+use image::{ImageBuffer, Pixel, Primitive};
+use num_traits::NumCast;
+use std::ops::DerefMut;
+
+/// How an [AggregateMerger](AggregateMerger) combines the images pushed onto it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reduce {
+    /// Keeps the smallest value of each channel.
+    Min,
+    /// Keeps the largest value of each channel.
+    Max,
+    /// Keeps the average value of each channel, rounded to the nearest value for integer subpixels.
+    Mean,
+}
+
+/// A merger that, instead of laying images out in a grid, reduces every image pushed onto it into a single image of the
+/// same dimensions. Each channel of each pixel is combined with the chosen [Reduce](Reduce), so the canvas is, for example,
+/// the pixel-wise maximum of every image pushed so far. Only the running result is kept, not the images themselves, which
+/// makes this useful for statistical overlays of a large number of images.
+///
+/// # Type Parameters
+/// * `P` - The pixel type of the underlying image.
+///
+/// # Example
+/// ```
+/// use image_merger::{AggregateMerger, Merger, Image, Reduce, Rgb};
+///
+/// let mut merger: AggregateMerger<Rgb<u8>> = AggregateMerger::new((100, 100), Reduce::Max);
+/// merger.push(&Image::new_from_pixel(100, 100, Rgb([10, 200, 30])));
+/// merger.push(&Image::new_from_pixel(100, 100, Rgb([50, 20, 30])));
+/// assert_eq!(*merger.get_canvas().get_pixel(0, 0), Rgb([50, 200, 30]));
+/// ```
+pub struct AggregateMerger<P>
+where
+    P: Pixel,
+{
+    canvas: BufferedImage<P>,
+    reduce: Reduce,
+    sums: Vec<f64>,  // The running sum of each subpixel, only kept for `Reduce::Mean`.
+    num_images: u32, // The number of images that have been reduced into the canvas.
+}
+
+impl<P> AggregateMerger<P>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    /// Constructs a new AggregateMerger.
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions, (x, y), of the images being reduced, which is also the size of the canvas.
+    /// * `reduce` - How the images are combined.
+    pub fn new(image_dimensions: (u32, u32), reduce: Reduce) -> Self {
+        let canvas: BufferedImage<P> = Image::new(image_dimensions.0, image_dimensions.1);
+        let sums = match reduce {
+            Reduce::Mean => vec![0.0; canvas.len()],
+            Reduce::Min | Reduce::Max => Vec::new(),
+        };
+
+        Self {
+            canvas,
+            reduce,
+            sums,
+            num_images: 0,
+        }
+    }
+
+    /// Returns how the images pushed onto the merger are combined.
+    pub fn get_reduce(&self) -> Reduce {
+        self.reduce
+    }
+
+    /// Reduces an image into the canvas.
+    /// # Arguments
+    /// * `image` - The image to reduce into the canvas.
+    /// # Returns
+    /// * `Ok` - If the image was reduced.
+    /// * `Err(MergerError::DimensionMismatch)` - If the dimensions of the image differ from the canvas.
+    pub fn try_push<SourceContainer>(
+        &mut self,
+        image: &Image<P, ImageBuffer<P, SourceContainer>>,
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        if image.dimensions() != self.canvas.dimensions() {
+            return Err(MergerError::DimensionMismatch {
+                index: None,
+                expected: self.canvas.dimensions(),
+                got: image.dimensions(),
+            });
+        }
+
+        self.num_images += 1;
+        let canvas: &mut [P::Subpixel] = &mut self.canvas;
+        // The container of the image may be longer than the image itself, so only the subpixels of the image are read.
+        let source: &[P::Subpixel] = image;
+        let source = &source[..canvas.len()];
+
+        // The canvas starts out zeroed, so the first image is copied rather than compared against it.
+        if self.num_images == 1 && self.reduce != Reduce::Mean {
+            canvas.copy_from_slice(source);
+            return Ok(());
+        }
+
+        match self.reduce {
+            Reduce::Min => {
+                canvas
+                    .par_iter_mut()
+                    .zip(source.par_iter())
+                    .for_each(|(value, &other)| {
+                        if other < *value {
+                            *value = other;
+                        }
+                    })
+            }
+            Reduce::Max => {
+                canvas
+                    .par_iter_mut()
+                    .zip(source.par_iter())
+                    .for_each(|(value, &other)| {
+                        if other > *value {
+                            *value = other;
+                        }
+                    })
+            }
+            Reduce::Mean => {
+                let count = self.num_images as f64;
+                // Floating point subpixels have a maximum value of 1.0 and must not be rounded.
+                let max: f64 =
+                    NumCast::from(<P::Subpixel as Primitive>::DEFAULT_MAX_VALUE).unwrap();
+
+                self.sums
+                    .par_iter_mut()
+                    .zip(canvas.par_iter_mut())
+                    .zip(source.par_iter())
+                    .for_each(|((sum, value), &other)| {
+                        *sum += NumCast::from(other).unwrap_or(0.0f64);
+                        let mean = *sum / count;
+                        let mean = if max > 1.0 { mean.round() } else { mean };
+                        *value = NumCast::from(mean).unwrap();
+                    });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reduces each image into the canvas, in order. Every image is validated before any are reduced.
+    /// # Arguments
+    /// * `images` - The images to reduce into the canvas.
+    /// # Returns
+    /// * `Ok` - If the images were reduced.
+    /// * `Err(MergerError::DimensionMismatch)` - If the dimensions of an image differ from the canvas.
+    pub fn try_bulk_push<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, ImageBuffer<P, SourceContainer>>],
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        if let Some(index) = images
+            .iter()
+            .position(|image| image.dimensions() != self.canvas.dimensions())
+        {
+            return Err(MergerError::DimensionMismatch {
+                index: Some(index as u32),
+                expected: self.canvas.dimensions(),
+                got: images[index].dimensions(),
+            });
+        }
+
+        images.iter().try_for_each(|image| self.try_push(image))
+    }
+}
+
+impl<P> Merger<P, Vec<P::Subpixel>> for AggregateMerger<P>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    fn get_canvas(&self) -> &BufferedImage<P> {
+        &self.canvas
+    }
+
+    /// Returns a mutable reference to the canvas. Note that with `Reduce::Mean`, the canvas is recomputed from the running
+    /// sums on every push, so any edits made here are overwritten by the next image.
+    fn get_canvas_mut(&mut self) -> &mut BufferedImage<P> {
+        &mut self.canvas
+    }
+
+    fn into_canvas(self) -> BufferedImage<P> {
+        self.canvas
+    }
+
+    fn get_num_images(&self) -> u32 {
+        self.num_images
+    }
+
+    fn push<SourceContainer>(&mut self, image: &Image<P, ImageBuffer<P, SourceContainer>>)
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        self.try_push(image)
+            .expect("The image could not be reduced into the canvas!");
+    }
+
+    fn bulk_push<SourceContainer>(&mut self, images: &[&Image<P, ImageBuffer<P, SourceContainer>>])
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        self.try_bulk_push(images)
+            .expect("The images could not be reduced into the canvas!");
+    }
+}
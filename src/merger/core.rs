@@ -1,6 +1,18 @@
-use crate::core::Image;
-use image::Pixel;
-use std::{marker::Sync, ops::DerefMut};
+use crate::{
+    core::{AlphaPixel, Image},
+    functions, BufferedImage,
+};
+use image::{
+    codecs::{jpeg::JpegEncoder, png::PngEncoder},
+    imageops::FilterType,
+    DynamicImage, EncodableLayout, ImageBuffer, ImageEncoder, ImageFormat, Luma, Pixel,
+    PixelWithColorType,
+};
+use std::{
+    io::{Cursor, Write},
+    marker::Sync,
+    ops::DerefMut,
+};
 
 /// Represents a point on any canvas.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -9,15 +21,223 @@ pub struct Point {
     pub y: u32,
 }
 
+/// Returns the top left point and the dimensions of the smallest rectangle that contains every given rectangle, or an
+/// empty rectangle at the origin if there are none.
+pub(crate) fn bounding_box(
+    rects: impl Iterator<Item = (Point, (u32, u32))>,
+) -> (Point, (u32, u32)) {
+    let bounds = rects.fold(
+        None,
+        |bounds: Option<(u32, u32, u32, u32)>, (point, (width, height))| {
+            let (left, top, right, bottom) = (point.x, point.y, point.x + width, point.y + height);
+            Some(match bounds {
+                None => (left, top, right, bottom),
+                Some((l, t, r, b)) => (l.min(left), t.min(top), r.max(right), b.max(bottom)),
+            })
+        },
+    );
+
+    match bounds {
+        Some((left, top, right, bottom)) => {
+            (Point { x: left, y: top }, (right - left, bottom - top))
+        }
+        None => (Point { x: 0, y: 0 }, (0, 0)),
+    }
+}
+
 /// Represents the padding between images on a canvas.
 /// # Fields
 /// * `x` - The padding between images on the x axis.
 /// * `y` - The padding between images on the y axis.
 pub type Padding = Point;
 
+/// Represents the padding around each side of every image on a canvas. Unlike [Padding](Padding), which only adds space
+/// between neighbouring images, this adds space on every side of each image, so the sides can differ from one another.
+/// # Fields
+/// * `top` - The padding above each image.
+/// * `right` - The padding to the right of each image.
+/// * `bottom` - The padding below each image.
+/// * `left` - The padding to the left of each image.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EdgePadding {
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+    pub left: u32,
+}
+
+/// The gutters, or empty space, around the images on a canvas. This can be created from either a [Padding](Padding) or
+/// an [EdgePadding](EdgePadding).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gutters {
+    /// Uniform padding between neighbouring images.
+    Between(Padding),
+    /// Padding around each side of every image.
+    Edges(EdgePadding),
+}
+
+impl From<Padding> for Gutters {
+    fn from(padding: Padding) -> Self {
+        Gutters::Between(padding)
+    }
+}
+
+impl From<EdgePadding> for Gutters {
+    fn from(edge_padding: EdgePadding) -> Self {
+        Gutters::Edges(edge_padding)
+    }
+}
+
+/// The direction images are laid out in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Images are laid out left to right, in a single row.
+    Horizontal,
+    /// Images are laid out top to bottom, in a single column.
+    Vertical,
+}
+
+/// Where an image smaller than its cell is placed within the cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Anchor {
+    /// Pins the image to the top left corner of the cell.
+    TopLeft,
+    /// Pins the image to the top right corner of the cell.
+    TopRight,
+    /// Centers the image in the cell. When the image does not split evenly, the extra pixel goes to the right and bottom.
+    #[default]
+    Center,
+    /// Pins the image to the bottom left corner of the cell.
+    BottomLeft,
+    /// Pins the image to the bottom right corner of the cell.
+    BottomRight,
+}
+
+impl Anchor {
+    /// Returns the offset of an image with the given dimensions from the top left of a cell with the given dimensions.
+    pub(crate) fn offset(self, image: (u32, u32), cell: (u32, u32)) -> Point {
+        let (free_x, free_y) = (cell.0 - image.0, cell.1 - image.1);
+        let (x, y) = match self {
+            Anchor::TopLeft => (0, 0),
+            Anchor::TopRight => (free_x, 0),
+            Anchor::Center => (free_x / 2, free_y / 2),
+            Anchor::BottomLeft => (0, free_y),
+            Anchor::BottomRight => (free_x, free_y),
+        };
+
+        Point { x, y }
+    }
+}
+
+/// How an image is fit into a cell with a different aspect ratio when it is resized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FitMode {
+    /// Stretches the image to the dimensions of the cell, distorting it if the aspect ratios differ.
+    #[default]
+    Stretch,
+    /// Shrinks or grows the image until it fits inside the cell, keeping its aspect ratio. The image is centered, and the
+    /// bars left on either side of it are filled.
+    Contain,
+    /// Shrinks or grows the image until it covers the whole cell, keeping its aspect ratio. The image is centered, and
+    /// whatever extends past the cell is cropped off.
+    Cover,
+}
+
+impl FitMode {
+    /// Returns the dimensions an image with the given dimensions is resized to before it is fit into a cell with the given
+    /// dimensions. Each side is rounded to the nearest pixel, and is never less than 1.
+    pub(crate) fn fitted_dimensions(self, image: (u32, u32), cell: (u32, u32)) -> (u32, u32) {
+        let (width, height) = (image.0 as u64, image.1 as u64);
+        let (cell_width, cell_height) = (cell.0 as u64, cell.1 as u64);
+        if width == 0 || height == 0 {
+            return cell;
+        }
+
+        // Scales `side` by `numerator / denominator` in u64 so large images do not overflow.
+        let scale = |side: u64, numerator: u64, denominator: u64| {
+            ((side * numerator + denominator / 2) / denominator).max(1) as u32
+        };
+        let wider = width * cell_height >= height * cell_width;
+        match (self, wider) {
+            (FitMode::Stretch, _) => cell,
+            (FitMode::Contain, true) => (cell.0, scale(height, cell_width, width)),
+            (FitMode::Contain, false) => (scale(width, cell_height, height), cell.1),
+            (FitMode::Cover, true) => (scale(width, cell_height, height).max(cell.0), cell.1),
+            (FitMode::Cover, false) => (cell.0, scale(height, cell_width, width).max(cell.1)),
+        }
+    }
+}
+
+/// The filter used to sample an image when it is resized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResampleFilter {
+    /// Takes the closest pixel. This is by far the fastest, but gives blocky results when shrinking photos.
+    #[default]
+    Nearest,
+    /// Interpolates linearly between the closest pixels, which smooths out the result.
+    Bilinear,
+    /// Interpolates with a Catmull-Rom cubic over a wider area, which keeps edges sharper than `Bilinear`.
+    Bicubic,
+    /// Interpolates with a Lanczos window of 3 lobes. This is the slowest, but gives the sharpest results.
+    Lanczos3,
+}
+
+impl ResampleFilter {
+    /// Returns the filter of the `image` crate this filter samples with.
+    pub(crate) fn filter_type(self) -> FilterType {
+        match self {
+            ResampleFilter::Nearest => FilterType::Nearest,
+            ResampleFilter::Bilinear => FilterType::Triangle,
+            ResampleFilter::Bicubic => FilterType::CatmullRom,
+            ResampleFilter::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+/// The color space images with alpha are blended onto the canvas in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// Blends the sRGB encoded values directly. This is the fastest, but darkens the edges between contrasting colors.
+    #[default]
+    Srgb,
+    /// Converts the sRGB encoded values to linear light, blends them, then converts them back. This gives physically
+    /// correct results.
+    Linear,
+}
+
+/// An orientation transform that can be applied to an image before it is pasted onto a canvas. Rotations are clockwise.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Transform {
+    /// Leaves the image as is.
+    #[default]
+    None,
+    /// Mirrors the image horizontally.
+    FlipH,
+    /// Mirrors the image vertically.
+    FlipV,
+    /// Rotates the image 90 degrees, swapping its width and height.
+    Rotate90,
+    /// Rotates the image 180 degrees.
+    Rotate180,
+    /// Rotates the image 270 degrees, swapping its width and height.
+    Rotate270,
+}
+
+impl Transform {
+    /// Returns the dimensions of an image with the given dimensions after this transform is applied to it.
+    pub fn transformed_dimensions(&self, (width, height): (u32, u32)) -> (u32, u32) {
+        match self {
+            Transform::Rotate90 | Transform::Rotate270 => (height, width),
+            _ => (width, height),
+        }
+    }
+}
+
 /// The Merger trait that all mergers must implement. This trait allows the merger to paste images to a canvas.
 /// # Type Parameters
 /// * `P` - The pixel type of the underlying image.
+/// * `Container` - The container backing the canvas, which is handed back as-is by `into_canvas`. This allows mergers
+///   built on borrowed or otherwise non-Vec buffers to recover their buffer without copying it.
 pub trait Merger<P, Container>
 where
     P: Pixel + Sync,
@@ -27,21 +247,238 @@ where
     /// Returns a reference to the underlying canvas.
     fn get_canvas(&self) -> &Image<P, image::ImageBuffer<P, Container>>;
 
+    /// Returns a mutable reference to the underlying canvas. This can be used to draw onto the canvas, such as annotations
+    /// or borders, without consuming the merger.
+    fn get_canvas_mut(&mut self) -> &mut Image<P, image::ImageBuffer<P, Container>>;
+
     /// Consumes the underlying merger and returns the canvas.
     fn into_canvas(self) -> Image<P, image::ImageBuffer<P, Container>>;
 
+    /// Returns the number of images that have been pasted to the canvas.
+    fn get_num_images(&self) -> u32;
+
+    /// Returns the dimensions, (width, height), of the whole canvas.
+    fn canvas_dimensions(&self) -> (u32, u32) {
+        self.get_canvas().dimensions()
+    }
+
+    /// Returns whether no images have been pasted to the canvas yet.
+    fn is_empty(&self) -> bool {
+        self.get_num_images() == 0
+    }
+
+    /// Returns the pixel at the given coordinates of the canvas, such as for spot checking the result of a merge.
+    /// # Arguments
+    /// * `x` - The x coordinate of the pixel.
+    /// * `y` - The y coordinate of the pixel.
+    /// # Returns
+    /// * `Some(pixel)` - The pixel at the given coordinates.
+    /// * `None` - If the coordinates are outside of the canvas.
+    fn canvas_pixel(&self, x: u32, y: u32) -> Option<P> {
+        self.get_canvas().get_pixel_checked(x, y).copied()
+    }
+
     /// Allows the merger to push an image to the canvas. This can be used in a loop to paste a large number of images without
     /// having to hold all them in memory.
     /// # Arguments
-    /// * `image` - The image to push onto the canvas. Its pixel type, `P`, must match the canvas, and its `Container` must be dereferenceable to
-    /// a slice of `P::Subpixel`s.
-    fn push(&mut self, image: &Image<P, image::ImageBuffer<P, Container>>);
+    /// * `image` - The image to push onto the canvas. Its pixel type, `P`, must match the canvas, and its `SourceContainer` must be
+    ///   dereferenceable to a slice of `P::Subpixel`s. The container does not need to match the canvas' own, so `Vec` backed
+    ///   images can be pushed onto a canvas backed by borrowed memory.
+    fn push<SourceContainer>(&mut self, image: &Image<P, image::ImageBuffer<P, SourceContainer>>)
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync;
 
     /// Allows the merger to bulk push N images to the canvas. This is useful for when you have a large number of images to paste.
     /// The downside is that you have to hold all of the images in memory at once, which can be a problem if you have a large number of images.
     /// # Arguments
     /// * `images` - The images to push onto the canvas. Note that the argument type is `&[&Image<...>]`, the func
-    /// does not need to take ownership of the images, it only needs to read them. The pixel type, `P`, of the images must match the canvas, and
-    /// their `Container` must be dereferenceable to a slice of `P::Subpixel`s.
-    fn bulk_push(&mut self, images: &[&Image<P, image::ImageBuffer<P, Container>>]);
+    ///   does not need to take ownership of the images, it only needs to read them. The pixel type, `P`, of the images must match the canvas, and
+    ///   their `SourceContainer` must be dereferenceable to a slice of `P::Subpixel`s. As with `push`, it does not need to match the
+    ///   canvas' own container.
+    fn bulk_push<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+    ) where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync;
+
+    /// Returns the subpixels backing the canvas as one contiguous slice, without copying them. This is useful for handing the
+    /// canvas to other code, such as a GPU uploader, that works on raw buffers. The subpixels are laid out row by row, top to
+    /// bottom, with the channels of each pixel interleaved. Each row is `width * P::CHANNEL_COUNT` subpixels long, and there
+    /// is no padding between rows. Containers longer than the canvas, such as one given to `new_from_raw`, are cut down to
+    /// `width * height * P::CHANNEL_COUNT` subpixels.
+    fn as_subpixels<'a>(&'a self) -> &'a [P::Subpixel]
+    where
+        P: 'a,
+        Container: 'a,
+    {
+        let canvas = self.get_canvas();
+        let len = canvas.width() as usize * canvas.height() as usize * P::CHANNEL_COUNT as usize;
+        let subpixels: &[P::Subpixel] = canvas;
+        &subpixels[..len]
+    }
+
+    /// Same as `as_subpixels`, but returns a mutable slice so the canvas can be written to directly.
+    fn as_subpixels_mut<'a>(&'a mut self) -> &'a mut [P::Subpixel]
+    where
+        P: 'a,
+        Container: 'a,
+    {
+        let canvas = self.get_canvas_mut();
+        let len = canvas.width() as usize * canvas.height() as usize * P::CHANNEL_COUNT as usize;
+        let subpixels: &mut [P::Subpixel] = canvas;
+        &mut subpixels[..len]
+    }
+
+    /// Returns the size of the canvas buffer in bytes, which is `width * height * P::CHANNEL_COUNT * size_of::<P::Subpixel>()`.
+    /// This is useful for keeping track of how much memory a merger is holding onto.
+    fn memory_footprint(&self) -> usize {
+        std::mem::size_of_val(self.as_subpixels())
+    }
+
+    /// Converts the canvas to a single channel `Luma<u8>` image, such as for feeding into pipelines that expect grayscale
+    /// input. The conversion is done in parallel with the Rec. 601 weights, and grayscale canvases keep their values.
+    fn to_luma(&self) -> BufferedImage<Luma<u8>> {
+        functions::to_luma(self.get_canvas())
+    }
+
+    /// Converts the canvas to premultiplied alpha, such as for handing it to a compositor that expects it. Each color channel
+    /// is multiplied by `alpha / 255` in parallel. This is only available for pixel types with an alpha channel, and can be
+    /// reversed with `Image::from_premultiplied`.
+    /// # Returns
+    /// A copy of the canvas with premultiplied alpha.
+    fn to_premultiplied(&self) -> BufferedImage<P>
+    where
+        P: AlphaPixel,
+    {
+        functions::premultiply(self.get_canvas())
+    }
+
+    /// Resizes the canvas into a preview image whose longest side is `max_dim` pixels, keeping the aspect ratio of the canvas.
+    /// The canvas itself is left untouched.
+    /// # Arguments
+    /// * `max_dim` - The length of the longest side of the preview.
+    /// # Returns
+    /// The resized preview of the canvas.
+    fn thumbnail(&self, max_dim: u32) -> BufferedImage<P> {
+        let canvas = self.get_canvas();
+        let (width, height) = canvas.dimensions();
+        let longest = width.max(height).max(1) as u64;
+
+        // Scale the shorter side in u64 to avoid overflowing, and never let it collapse to 0.
+        let scale =
+            |side: u32| (((side as u64 * max_dim as u64) + longest / 2) / longest).max(1) as u32;
+
+        functions::resize_nearest_neighbor(canvas, scale(width), scale(height))
+    }
+
+    /// Returns a copy of the canvas cropped to the bounding box of the images pasted onto it, so a partially filled canvas can
+    /// be exported without its blank trailing rows. Mergers that do not lay images out in cells return a copy of the whole
+    /// canvas.
+    /// # Returns
+    /// The cropped copy of the canvas, which is empty if no images have been pasted.
+    fn trim_to_content(&self) -> BufferedImage<P>
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        let canvas = self.get_canvas();
+        functions::crop(canvas, Point { x: 0, y: 0 }, canvas.dimensions())
+    }
+
+    /// Fills a rectangle of the canvas with the given pixel, in parallel rows. Unlike removing an image, the rectangle does not
+    /// need to line up with the cells of the canvas, so a region spanning several cells can be blanked at once. The rectangle
+    /// is clipped to the canvas. The number of images on the canvas is not changed.
+    /// # Arguments
+    /// * `x` - The x coordinate of the top left corner of the rectangle.
+    /// * `y` - The y coordinate of the top left corner of the rectangle.
+    /// * `width` - The width of the rectangle.
+    /// * `height` - The height of the rectangle.
+    /// * `fill` - The pixel to fill the rectangle with.
+    fn clear_rect(&mut self, x: u32, y: u32, width: u32, height: u32, fill: P)
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        functions::fill_rect(self.get_canvas_mut(), Point { x, y }, (width, height), fill);
+    }
+
+    /// Consumes the merger and wraps the canvas in the [DynamicImage](image::DynamicImage) variant matching its pixel type,
+    /// such as `ImageRgba8` for `Rgba<u8>`, for handing it to crates that expect one. The canvas buffer is moved rather than
+    /// copied. This is only available for `Vec` based canvases with a pixel type that `DynamicImage` supports.
+    /// # Returns
+    /// The canvas as a `DynamicImage`.
+    fn into_dynamic(self) -> DynamicImage
+    where
+        Self: Sized,
+        ImageBuffer<P, Container>: Into<DynamicImage>,
+    {
+        self.into_canvas().into_buffer().into()
+    }
+
+    /// Same as `into_dynamic`, but copies the canvas so the merger can keep being used. This works for any container type.
+    /// # Returns
+    /// A copy of the canvas as a `DynamicImage`.
+    fn to_dynamic(&self) -> DynamicImage
+    where
+        ImageBuffer<P, Vec<P::Subpixel>>: Into<DynamicImage>,
+    {
+        let (width, height) = self.canvas_dimensions();
+        ImageBuffer::<P, Vec<P::Subpixel>>::from_raw(width, height, self.as_subpixels().to_vec())
+            .expect("The canvas always holds every one of its subpixels!")
+            .into()
+    }
+
+    /// Encodes the canvas into an in-memory buffer with the given format. This avoids a round trip through the filesystem
+    /// when the merged image is going to be sent elsewhere.
+    /// # Arguments
+    /// * `format` - The format to encode the canvas with.
+    /// # Returns
+    /// The encoded bytes, or the [image::ImageError](image::ImageError) that occurred while encoding.
+    fn encode_to_vec(&self, format: ImageFormat) -> image::ImageResult<Vec<u8>>
+    where
+        P: PixelWithColorType,
+        [P::Subpixel]: EncodableLayout,
+    {
+        let mut buffer = Cursor::new(Vec::new());
+        self.get_canvas().write_to(&mut buffer, format)?;
+
+        Ok(buffer.into_inner())
+    }
+
+    /// Encodes the canvas as a PNG straight into the given writer. The subpixels of the canvas are handed to the encoder as
+    /// they are, so unlike going through a `DynamicImage`, no copy of the canvas is made. This matters for very large canvases.
+    /// # Arguments
+    /// * `writer` - Where to write the encoded PNG, such as a file.
+    /// # Returns
+    /// * `Ok` - If the canvas was written.
+    /// * `Err` - The [image::ImageError](image::ImageError) that occurred while encoding or writing.
+    fn write_png<W: Write>(&self, writer: W) -> image::ImageResult<()>
+    where
+        P: PixelWithColorType,
+        [P::Subpixel]: EncodableLayout,
+    {
+        let canvas = self.get_canvas();
+        let (width, height) = canvas.dimensions();
+        PngEncoder::new(writer).write_image(
+            self.as_subpixels().as_bytes(),
+            width,
+            height,
+            P::COLOR_TYPE,
+        )
+    }
+
+    /// Encodes the canvas into an in-memory JPEG with the given quality.
+    /// # Arguments
+    /// * `quality` - The JPEG quality, from 1 (worst) to 100 (best).
+    /// # Returns
+    /// The encoded bytes, or the [image::ImageError](image::ImageError) that occurred while encoding.
+    fn encode_jpeg(&self, quality: u8) -> image::ImageResult<Vec<u8>>
+    where
+        P: PixelWithColorType,
+        [P::Subpixel]: EncodableLayout,
+    {
+        let mut buffer = Vec::new();
+        self.get_canvas()
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut buffer, quality))?;
+
+        Ok(buffer)
+    }
 }
@@ -0,0 +1,244 @@
+use super::core::{Anchor, FitMode, Merger, Padding, ResampleFilter};
+use crate::{
+    functions::{crop, resize_nearest_neighbor},
+    parallel::*,
+    BufferedImage, Image, KnownSizeMerger,
+};
+
+use image::{ImageBuffer, Pixel};
+use std::ops::DerefMut;
+
+/// A merger that lays images out in a fixed grid, resizing every image to the same dimensions before pasting it. Unlike
+/// [KnownSizeMerger](crate::KnownSizeMerger), images of any size can be pushed, which is useful when the source images come
+/// from different places and are not guaranteed to be uniform.
+///
+/// # Type Parameters
+/// * `P` - The pixel type of the underlying image.
+///
+/// # Example
+/// ```
+/// use image_merger::{FixedSizeMerger, Merger, Image, Rgb};
+///
+/// // Create a canvas that holds 2 rows of 5 100x100 images.
+/// let mut merger: FixedSizeMerger<Rgb<u8>> = FixedSizeMerger::new((100, 100), 5, 2, None);
+///
+/// // Push a 500x500 sized image onto the canvas. It will automatically be resized to 100x100.
+/// merger.push(&Image::new(500, 500));
+/// ```
+pub struct FixedSizeMerger<P>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+{
+    merger: KnownSizeMerger<P, Vec<P::Subpixel>>,
+    filter: ResampleFilter, // The filter images are resized with.
+    fit: FitMode,           // How images with a different aspect ratio are fit into their cells.
+    fill: Option<P>, // The color of the bars around images fit with `FitMode::Contain`, None to leave them zeroed.
+}
+
+impl<P> FixedSizeMerger<P>
+where
+    P: Pixel + Sync + Send + 'static,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    /// Constructs a new FixedSizeMerger.
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions every image is resized to before being pasted.
+    /// * `images_per_row` - The number of images per row.
+    /// * `total_rows` - The number of rows on the canvas.
+    /// * `padding` - The padding between images, or None for no padding.
+    pub fn new(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_rows: u32,
+        padding: Option<Padding>,
+    ) -> Self {
+        Self {
+            merger: KnownSizeMerger::new(
+                image_dimensions,
+                images_per_row,
+                images_per_row * total_rows,
+                padding,
+            ),
+            filter: ResampleFilter::Nearest,
+            fit: FitMode::Stretch,
+            fill: None,
+        }
+    }
+
+    /// Constructs a new FixedSizeMerger that fits every image into its cell with the given mode and filter. This is a one stop
+    /// way to build a tidy contact sheet from photos of any size, such as with `FitMode::Contain` and
+    /// `ResampleFilter::Bilinear`.
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of each cell.
+    /// * `images_per_row` - The number of images per row.
+    /// * `total_rows` - The number of rows on the canvas.
+    /// * `padding` - The padding between images, or None for no padding.
+    /// * `fit` - How images with a different aspect ratio are fit into their cells.
+    /// * `filter` - The filter to resize images with.
+    /// * `fill` - The color of the bars around images fit with `FitMode::Contain`.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{FitMode, FixedSizeMerger, Merger, Image, ResampleFilter, Rgb};
+    ///
+    /// let mut merger: FixedSizeMerger<Rgb<u8>> = FixedSizeMerger::with(
+    ///     (100, 100), 5, 2, None, FitMode::Contain, ResampleFilter::Bilinear, Rgb([255, 255, 255]),
+    /// );
+    ///
+    /// // The 400x200 image is shrunk to 100x50 and centered, with white bars above and below it.
+    /// merger.push(&Image::new(400, 200));
+    /// assert_eq!(merger.canvas_pixel(50, 10), Some(Rgb([255, 255, 255])));
+    /// assert_eq!(merger.canvas_pixel(50, 50), Some(Rgb([0, 0, 0])));
+    /// ```
+    pub fn with(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_rows: u32,
+        padding: Option<Padding>,
+        fit: FitMode,
+        filter: ResampleFilter,
+        fill: P,
+    ) -> Self {
+        Self::new(image_dimensions, images_per_row, total_rows, padding)
+            .with_filter(filter)
+            .with_fit(fit, fill)
+    }
+
+    /// Sets the filter images are resized with. Defaults to `ResampleFilter::Nearest`, which is by far the fastest, but
+    /// smoother filters such as `ResampleFilter::Bilinear` give better results when shrinking photos into small cells.
+    /// # Arguments
+    /// * `filter` - The filter to resize images with.
+    pub fn with_filter(mut self, filter: ResampleFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Sets how images with a different aspect ratio than their cell are fit into it. Defaults to `FitMode::Stretch`.
+    /// # Arguments
+    /// * `fit` - How images are fit into their cells.
+    /// * `fill` - The color of the bars around images fit with `FitMode::Contain`.
+    pub fn with_fit(mut self, fit: FitMode, fill: P) -> Self {
+        self.fit = fit;
+        self.fill = Some(fill);
+        self
+    }
+
+    /// Returns how images with a different aspect ratio than their cell are fit into it.
+    pub fn get_fit(&self) -> FitMode {
+        self.fit
+    }
+
+    /// Resizes an image to the image dimensions of the merger with the filter and fit mode of the merger.
+    fn resize<SourceContainer>(
+        &self,
+        image: &Image<P, ImageBuffer<P, SourceContainer>>,
+    ) -> BufferedImage<P>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let cell = self.get_image_dimensions();
+        let fitted = self.fit.fitted_dimensions(image.dimensions(), cell);
+        let resized = self.resize_to(image, fitted);
+
+        match self.fit {
+            FitMode::Stretch => resized,
+            FitMode::Contain => {
+                let mut letterboxed = match self.fill {
+                    Some(fill) => BufferedImage::new_from_pixel(cell.0, cell.1, fill),
+                    None => BufferedImage::new(cell.0, cell.1),
+                };
+                let offset = Anchor::Center.offset(fitted, cell);
+                image::imageops::replace(
+                    &mut *letterboxed,
+                    &*resized,
+                    offset.x as i64,
+                    offset.y as i64,
+                );
+                letterboxed
+            }
+            FitMode::Cover => crop(&resized, Anchor::Center.offset(cell, fitted), cell),
+        }
+    }
+
+    /// Resizes an image to the given dimensions with the filter of the merger.
+    fn resize_to<SourceContainer>(
+        &self,
+        image: &Image<P, ImageBuffer<P, SourceContainer>>,
+        (width, height): (u32, u32),
+    ) -> BufferedImage<P>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        match self.filter {
+            ResampleFilter::Nearest => resize_nearest_neighbor(image, width, height),
+            filter => BufferedImage::from(image::imageops::resize(
+                &**image,
+                width,
+                height,
+                filter.filter_type(),
+            )),
+        }
+    }
+
+    /// Returns the dimensions, (x, y), every image is resized to before being pasted.
+    pub fn get_image_dimensions(&self) -> (u32, u32) {
+        self.merger.get_image_dimensions()
+    }
+
+    /// Returns whether an image of the given dimensions will be resized when it is pushed, which is the case when they differ
+    /// from the dimensions every image is resized to.
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions, (x, y), of the image.
+    pub fn needs_resize(&self, image_dimensions: (u32, u32)) -> bool {
+        self.merger.needs_resize(image_dimensions)
+    }
+}
+
+impl<P> Merger<P, Vec<P::Subpixel>> for FixedSizeMerger<P>
+where
+    P: Pixel + Sync + Send + 'static,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    fn get_canvas(&self) -> &BufferedImage<P> {
+        self.merger.get_canvas()
+    }
+
+    fn get_canvas_mut(&mut self) -> &mut BufferedImage<P> {
+        self.merger.get_canvas_mut()
+    }
+
+    fn into_canvas(self) -> BufferedImage<P> {
+        self.merger.into_canvas()
+    }
+
+    fn get_num_images(&self) -> u32 {
+        self.merger.get_num_images()
+    }
+
+    fn trim_to_content(&self) -> BufferedImage<P> {
+        self.merger.trim_to_content()
+    }
+
+    fn push<SourceContainer>(&mut self, image: &Image<P, ImageBuffer<P, SourceContainer>>)
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        if self.needs_resize(image.dimensions()) {
+            let resized = self.resize(image);
+            self.merger.push(&resized);
+        } else {
+            self.merger.push(image);
+        }
+    }
+
+    fn bulk_push<SourceContainer>(&mut self, images: &[&Image<P, ImageBuffer<P, SourceContainer>>])
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        // Resizing an image to its own dimensions is a plain copy, so every image can take the same path.
+        let resized: Vec<BufferedImage<P>> =
+            images.par_iter().map(|image| self.resize(image)).collect();
+        self.merger.bulk_push(&resized.iter().collect::<Vec<_>>());
+    }
+}
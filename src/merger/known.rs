@@ -1,14 +1,100 @@
-use super::core::{Merger, Padding, Point};
+use super::core::{
+    bounding_box, Anchor, ColorSpace, EdgePadding, Gutters, Merger, Orientation, Padding, Point,
+    Transform,
+};
 use crate::{
     cell::ImageCell,
-    functions::{paste, resize_nearest_neighbor},
-    BufferedImage, Image, ResizableMerger,
+    functions::{
+        crop, is_fully_transparent, paste, paste_bgr, paste_blended, paste_masked, paste_region,
+        paste_sequential, resize_integer, resize_nearest_neighbor, tint, transform,
+        SEQUENTIAL_PASTE_THRESHOLD,
+    },
+    parallel::*,
+    AlphaPixel, BufferedImage, Image, MergerError, ResizableMerger, TryFromWithFormat,
 };
 
-use image::Pixel;
+use image::{
+    buffer::ConvertBuffer, codecs::gif::GifEncoder, Delay, EncodableLayout, Frame, ImageBuffer,
+    ImageFormat, Luma, Pixel, RgbaImage,
+};
 use num_traits::Zero;
-use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use std::ops::DerefMut;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    io::{self, Read, Write},
+    ops::{DerefMut, Range},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// Identifies a file written by `KnownSizeMerger::save_state`.
+const STATE_MAGIC: &[u8; 4] = b"IMST";
+
+/// The version of the format written by `KnownSizeMerger::save_state`. This must be bumped whenever the format changes.
+const STATE_VERSION: u8 = 1;
+
+/// Returns an error for a state file that could not be understood.
+fn invalid_state(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u8<R: Read>(reader: &mut R) -> io::Result<u8> {
+    let mut bytes = [0; 1];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes[0])
+}
+
+/// The most subpixels of the canvas `load_state` reads at a time. The canvas grows as its bytes arrive rather than being
+/// allocated up front, so a corrupted header can not make it allocate more than the reader actually holds.
+const STATE_READ_CHUNK: usize = 1 << 20;
+
+/// The reason given when a canvas would be larger than `u32::MAX` pixels along either axis.
+const CANVAS_TOO_LARGE: &str = "The canvas would be larger than u32::MAX pixels along an axis.";
+
+/// Checks that a layout describes a canvas with at least one cell, and that its images are not empty.
+fn validate_layout(
+    image_dimensions: (u32, u32),
+    images_per_row: u32,
+    total_images: u32,
+) -> Result<(), MergerError> {
+    let reason = if image_dimensions.0 == 0 || image_dimensions.1 == 0 {
+        "The image dimensions must be at least 1x1."
+    } else if images_per_row == 0 {
+        "There must be at least 1 image per row."
+    } else if total_images == 0 {
+        "There must be at least 1 image in total."
+    } else {
+        return Ok(());
+    };
+
+    Err(MergerError::InvalidLayout { reason })
+}
+
+/// Describes where an image ended up on the canvas of a [KnownSizeMerger](KnownSizeMerger), as returned by
+/// `KnownSizeMerger::manifest`.
+/// # Fields
+/// * `index` - The index of the cell the image was pasted into.
+/// * `x` - The x coordinate of the top left of the image on the canvas, including any padding.
+/// * `y` - The y coordinate of the top left of the image on the canvas, including any padding.
+/// * `width` - The width of the image.
+/// * `height` - The height of the image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellInfo {
+    pub index: u32,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
 
 /// A known size merger that allows you to paste images onto a canvas. This merger is useful when you already know the size
 /// of all the images being pushed onto the canvas. This merger has multiple implementations, one for any container type and
@@ -39,6 +125,235 @@ where
     last_pasted_index: i32, // The index of the last pasted image, starts at -1 if not images have been pasted.
     total_rows: u32,        // The total number of rows currently on the canvas.
     padding: Option<Padding>,
+    edge_padding: EdgePadding, // The padding around each side of every image.
+    margin: Point,             // The blank border around the whole grid.
+    background: Option<P>, // The color the canvas was filled with on creation, None if it was left zeroed.
+    thread_pool: Option<Arc<ThreadPool>>, // The pool to paste on, None to use the global rayon pool.
+    sequential_paste_threshold: u32, // Images with fewer pixels than this are pasted on the current thread.
+    occupied: Vec<bool>, // Whether each usable cell currently holds an image, one for each of the total images.
+    placement: Option<Placement>, // Where each index is pasted, None to lay the images out in a grid.
+    column_offsets: Vec<u32>,     // The x coordinate images in each column are pasted at.
+    row_offsets: Vec<u32>,        // The y coordinate images in each row are pasted at.
+}
+
+/// Maps the index of an image to the location it is pasted at on the canvas.
+type Placement = Arc<dyn Fn(u32) -> Point + Send + Sync>;
+
+/// Computes the (width, height) of a canvas that holds `total_rows` rows of `images_per_row` images, including the
+/// padding between them and around each of them, and the margin around the whole grid.
+fn canvas_dimensions(
+    image_dimensions: (u32, u32),
+    images_per_row: u32,
+    total_rows: u32,
+    padding: Option<&Padding>,
+    edge_padding: &EdgePadding,
+    margin: &Point,
+) -> Option<(u32, u32)> {
+    // Computed in u64 so that large grids can not silently wrap around to a tiny canvas.
+    let axis = |image: u32, before: u32, after: u32, count: u32, gap: u32, margin: u32| {
+        let cell = image as u64 + before as u64 + after as u64;
+        let length = cell * count as u64 + (count as u64).saturating_sub(1) * gap as u64;
+        u32::try_from(length + 2 * margin as u64).ok()
+    };
+
+    Some((
+        axis(
+            image_dimensions.0,
+            edge_padding.left,
+            edge_padding.right,
+            images_per_row,
+            padding.map(|p| p.x).unwrap_or(0),
+            margin.x,
+        )?,
+        axis(
+            image_dimensions.1,
+            edge_padding.top,
+            edge_padding.bottom,
+            total_rows,
+            padding.map(|p| p.y).unwrap_or(0),
+            margin.y,
+        )?,
+    ))
+}
+
+/// A builder for a [KnownSizeMerger](KnownSizeMerger) backed by a `Vec` container. This is a more readable alternative to
+/// the positional arguments of `KnownSizeMerger::new`, and allows setting options such as the background color of the canvas.
+///
+/// # Type Parameters
+/// * `P` - The pixel type of the underlying image.
+///
+/// # Example
+/// ```
+/// use image_merger::{KnownSizeMerger, KnownSizeMergerBuilder, Padding, Rgba};
+///
+/// let merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMergerBuilder::new()
+///     .image_dimensions((100, 100))
+///     .images_per_row(5)
+///     .total_images(10)
+///     .padding(Padding { x: 10, y: 10 })
+///     .background(Rgba([255, 255, 255, 255]))
+///     .build();
+/// ```
+pub struct KnownSizeMergerBuilder<P: Pixel> {
+    image_dimensions: Option<(u32, u32)>,
+    images_per_row: Option<u32>,
+    total_images: Option<u32>,
+    padding: Option<Padding>,
+    edge_padding: EdgePadding,
+    margin: Point,
+    background: Option<P>,
+    thread_pool: Option<Arc<ThreadPool>>,
+}
+
+impl<P: Pixel> Default for KnownSizeMergerBuilder<P> {
+    fn default() -> Self {
+        Self {
+            image_dimensions: None,
+            images_per_row: None,
+            total_images: None,
+            padding: None,
+            edge_padding: EdgePadding::default(),
+            margin: Point { x: 0, y: 0 },
+            background: None,
+            thread_pool: None,
+        }
+    }
+}
+
+impl<P> KnownSizeMergerBuilder<P>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+{
+    /// Creates a new, empty builder. The image dimensions, images per row, and total images must be set before calling `build`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the dimensions of the images being pasted (images must be a uniform size).
+    pub fn image_dimensions(mut self, image_dimensions: (u32, u32)) -> Self {
+        self.image_dimensions = Some(image_dimensions);
+        self
+    }
+
+    /// Sets the number of images per row.
+    pub fn images_per_row(mut self, images_per_row: u32) -> Self {
+        self.images_per_row = Some(images_per_row);
+        self
+    }
+
+    /// Sets the total number of images to be in the final canvas.
+    pub fn total_images(mut self, total_images: u32) -> Self {
+        self.total_images = Some(total_images);
+        self
+    }
+
+    /// Sets the padding between images. By default, there is no padding.
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    /// Sets the padding around each side of every image. By default, there is no padding around images.
+    pub fn edge_padding(mut self, edge_padding: EdgePadding) -> Self {
+        self.edge_padding = edge_padding;
+        self
+    }
+
+    /// Sets the blank margin around the whole grid, which is added to both sides of each axis. By default, the grid is flush
+    /// with the edges of the canvas.
+    pub fn margin(mut self, margin: Point) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Sets the gutters around images, from either a [Padding](Padding) or an [EdgePadding](EdgePadding).
+    pub fn gutters(self, gutters: impl Into<Gutters>) -> Self {
+        match gutters.into() {
+            Gutters::Between(padding) => self.padding(padding),
+            Gutters::Edges(edge_padding) => self.edge_padding(edge_padding),
+        }
+    }
+
+    /// Sets the color the canvas is filled with before any images are pasted. By default, the canvas is zeroed.
+    pub fn background(mut self, background: P) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    /// Sets the rayon thread pool used for pasting. By default, the global rayon thread pool is used.
+    #[cfg(feature = "rayon")]
+    pub fn thread_pool(mut self, thread_pool: Arc<ThreadPool>) -> Self {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
+    /// Builds the [KnownSizeMerger](KnownSizeMerger).
+    /// # Panics
+    /// This function will panic if the image dimensions, images per row, or total images have not been set, or if they do not
+    /// describe a valid canvas. Use `try_build` to handle invalid values instead.
+    pub fn build(self) -> KnownSizeMerger<P, Vec<P::Subpixel>> {
+        self.try_build()
+            .expect("The merger could not be created with the given parameters!")
+    }
+
+    /// Same as `build`, but returns an error instead of panicking if the values set do not describe a valid canvas.
+    /// # Returns
+    /// * `Ok` - If the merger was successfully created.
+    /// * `Err(MergerError::InvalidLayout)` - If the image dimensions, images per row, or total images are 0, or the canvas
+    ///   would be larger than `u32::MAX` pixels along either axis.
+    /// # Panics
+    /// This function will panic if the image dimensions, images per row, or total images have not been set.
+    pub fn try_build(self) -> Result<KnownSizeMerger<P, Vec<P::Subpixel>>, MergerError> {
+        let image_dimensions = self
+            .image_dimensions
+            .expect("The image dimensions must be set before building the merger.");
+        let images_per_row = self
+            .images_per_row
+            .expect("The images per row must be set before building the merger.");
+        let total_images = self
+            .total_images
+            .expect("The total images must be set before building the merger.");
+        validate_layout(image_dimensions, images_per_row, total_images)?;
+
+        let total_rows = total_images.div_ceil(images_per_row);
+        let (width, height) = canvas_dimensions(
+            image_dimensions,
+            images_per_row,
+            total_rows,
+            self.padding.as_ref(),
+            &self.edge_padding,
+            &self.margin,
+        )
+        .ok_or(MergerError::InvalidLayout {
+            reason: CANVAS_TOO_LARGE,
+        })?;
+
+        let canvas = match self.background {
+            Some(background) => Image::new_from_pixel(width, height, background),
+            None => Image::new(width, height),
+        };
+
+        Ok(KnownSizeMerger {
+            canvas: ImageCell::new(canvas),
+            image_dimensions,
+            num_images: 0,
+            images_per_row,
+            last_pasted_index: -1,
+            total_rows,
+            occupied: vec![false; total_images as usize],
+            padding: self.padding,
+            edge_padding: self.edge_padding,
+            margin: self.margin,
+            background: self.background,
+            thread_pool: self.thread_pool,
+            sequential_paste_threshold: SEQUENTIAL_PASTE_THRESHOLD,
+            placement: None,
+            column_offsets: Vec::new(),
+            row_offsets: Vec::new(),
+        }
+        .with_cached_offsets())
+    }
 }
 
 impl<P, Container> KnownSizeMerger<P, Container>
@@ -55,11 +370,12 @@ where
     /// * `total_images` - The total number of images to be in the final canvas.
     /// * `padding` - The padding between images, or None for no padding.
     /// * `container` - The container to use for the underlying canvas. This container must be big enough to hold all the potential images
-    /// that will be pasted to the canvas.
+    ///   that will be pasted to the canvas.
     ///
     /// # Returns
     /// * `Some` - If the merger was successfully created.
-    /// * `None` - If the merger could not be created. This will happen if the container is not large enough to fit all the images.
+    /// * `None` - If the merger could not be created. This will happen if the container is not large enough to fit all the images,
+    ///   if the layout has no cells or empty images, or if the canvas would be larger than `u32::MAX` pixels along either axis.
     ///
     /// # Example
     /// ```
@@ -75,229 +391,2839 @@ where
         padding: Option<Padding>,
         container: Container,
     ) -> Option<Self> {
-        let total_rows = (total_images + images_per_row - 1) / images_per_row;
+        Self::new_from_raw_checked(
+            image_dimensions,
+            images_per_row,
+            total_images,
+            padding,
+            container,
+        )
+        .ok()
+    }
 
-        let image_gaps_x = (images_per_row - 1) * padding.as_ref().map(|p| p.x).unwrap_or(0);
-        let image_gaps_y = (total_rows - 1) * padding.as_ref().map(|p| p.y).unwrap_or(0);
+    /// Same as `new_from_raw`, but returns why the merger could not be created.
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `padding` - The padding between images, or None for no padding.
+    /// * `container` - The container to use for the underlying canvas.
+    ///
+    /// # Returns
+    /// * `Ok` - If the merger was successfully created.
+    /// * `Err(MergerError::InvalidLayout)` - If the layout has no cells or empty images, or the canvas would be larger than
+    ///   `u32::MAX` pixels along either axis.
+    /// * `Err(MergerError::ContainerTooSmall)` - If the container can not hold the whole canvas.
+    pub fn new_from_raw_checked(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_images: u32,
+        padding: Option<Padding>,
+        container: Container,
+    ) -> Result<Self, MergerError> {
+        validate_layout(image_dimensions, images_per_row, total_images)?;
 
-        Image::new_from_raw(
-            (image_dimensions.0 * images_per_row) + image_gaps_x,
-            (image_dimensions.1 * total_rows) + image_gaps_y,
-            container,
+        let total_rows = total_images.div_ceil(images_per_row);
+        let (width, height) = canvas_dimensions(
+            image_dimensions,
+            images_per_row,
+            total_rows,
+            padding.as_ref(),
+            &EdgePadding::default(),
+            &Point { x: 0, y: 0 },
         )
-        .map(|canvas| Self {
+        .ok_or(MergerError::InvalidLayout {
+            reason: CANVAS_TOO_LARGE,
+        })?;
+
+        let needed = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(<P as Pixel>::CHANNEL_COUNT as usize))
+            .ok_or(MergerError::InvalidLayout {
+                reason: CANVAS_TOO_LARGE,
+            })?;
+        let got = container.len();
+        if got < needed {
+            return Err(MergerError::ContainerTooSmall { needed, got });
+        }
+
+        let canvas = Image::new_from_raw(width, height, container)
+            .expect("The container was checked to be large enough for the canvas!");
+        Ok(Self {
             canvas: ImageCell::new(canvas),
             image_dimensions,
             num_images: 0,
             images_per_row,
             last_pasted_index: -1,
             total_rows,
+            occupied: vec![false; total_images as usize],
             padding,
-        })
+            edge_padding: EdgePadding::default(),
+            margin: Point { x: 0, y: 0 },
+            background: None,
+            thread_pool: None,
+            sequential_paste_threshold: SEQUENTIAL_PASTE_THRESHOLD,
+            placement: None,
+            column_offsets: Vec::new(),
+            row_offsets: Vec::new(),
+        }
+        .with_cached_offsets())
     }
 
-    /// Returns the number of images that have been pasted to the canvas.
-    pub fn get_num_images(&self) -> u32 {
-        self.num_images
+    /// Sets the rayon thread pool the merger uses for its parallel work, such as pasting and resizing. By default, the global
+    /// rayon thread pool is used. This is useful to cap how many cores the merger can use.
+    /// # Arguments
+    /// * `thread_pool` - The thread pool to use.
+    #[cfg(feature = "rayon")]
+    pub fn with_thread_pool(mut self, thread_pool: Arc<ThreadPool>) -> Self {
+        self.thread_pool = Some(thread_pool);
+        self
     }
 
-    /// Returns the dimensions, (x, y), of the images being pasted to the canvas.
-    pub fn get_image_dimensions(&self) -> (u32, u32) {
-        self.image_dimensions
+    /// Sets the number of pixels below which an image is pasted on the current thread rather than across the thread pool, as
+    /// the overhead of parallelizing tiny pastes outweighs the work. Defaults to
+    /// [SEQUENTIAL_PASTE_THRESHOLD](crate::raw::SEQUENTIAL_PASTE_THRESHOLD). Setting this to 0 always pastes in parallel.
+    /// # Arguments
+    /// * `threshold` - The number of pixels below which images are pasted sequentially.
+    pub fn with_sequential_paste_threshold(mut self, threshold: u32) -> Self {
+        self.sequential_paste_threshold = threshold;
+        self
     }
 
-    #[inline(always)]
-    fn additional_space(&self) -> u32 {
-        (self.images_per_row * self.total_rows) - self.num_images
+    /// Pastes an image onto the canvas at the given location, picking the sequential path for images smaller than the
+    /// sequential paste threshold.
+    fn paste_image<SourceContainer>(
+        &self,
+        image: &Image<P, image::ImageBuffer<P, SourceContainer>>,
+        loc: Point,
+    ) where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        if (image.width() as u64 * image.height() as u64) < self.sequential_paste_threshold as u64 {
+            paste_sequential(&self.canvas, image, loc);
+        } else {
+            self.install(|| paste(&self.canvas, image, loc));
+        }
     }
 
-    fn get_paste_coordinates_unchecked(&self, index: u32) -> (u32, u32) {
-        let offset_x = index % self.images_per_row;
-        let offset_y = index / self.images_per_row;
+    /// Runs the given operation inside the merger's thread pool, or on the global rayon thread pool if none was set.
+    fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        match &self.thread_pool {
+            Some(thread_pool) => thread_pool.install(op),
+            None => op(),
+        }
+    }
 
-        let padding_x = self.padding.as_ref().map(|p| p.x).unwrap_or(0) * offset_x;
-        let padding_y = self.padding.as_ref().map(|p| p.y).unwrap_or(0) * offset_y;
+    /// Clears the canvas so the merger can be reused without reallocating it. Every pixel is reset to the background color
+    /// if one was set, or zeroed otherwise, and the next pushed image will be placed at index 0 again.
+    pub fn clear(&mut self)
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let fill: Vec<<P as Pixel>::Subpixel> = match self.background {
+            Some(background) => background.channels().to_vec(),
+            None => vec![Zero::zero(); channels],
+        };
 
-        let x = (offset_x * self.image_dimensions.0) + padding_x;
-        let y = (offset_y * self.image_dimensions.1) + padding_y;
+        // The container may be longer than the canvas, such as one given to `new_from_raw`, so only the canvas is cleared.
+        let len = self.canvas.width() as usize * self.canvas.height() as usize * channels;
+        let canvas: &mut [<P as Pixel>::Subpixel] = self.canvas.get_image_mut();
+        let canvas = &mut canvas[..len];
+        self.install(|| {
+            canvas
+                .par_chunks_exact_mut(channels)
+                .for_each(|pixel| pixel.copy_from_slice(&fill))
+        });
 
-        (x, y)
+        self.num_images = 0;
+        self.last_pasted_index = -1;
+        self.occupied.fill(false);
     }
 
-    fn get_next_paste_coordinates(&mut self) -> (u32, u32) {
-        if self.additional_space() == 0 {
-            panic!("No more space on the canvas!");
+    /// Paints a checkerboard pattern across the whole canvas, which is the familiar way of showing transparency. This should
+    /// be called before any images are pushed, as it paints over everything on the canvas. The square at the top left corner
+    /// of the canvas is `light`.
+    /// # Arguments
+    /// * `light` - The color of the light squares.
+    /// * `dark` - The color of the dark squares.
+    /// * `square` - The width and height of each square, in pixels.
+    /// # Panics
+    /// This function will panic if `square` is 0.
+    pub fn fill_background_checkerboard(&mut self, light: P, dark: P, square: u32)
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        assert!(
+            square > 0,
+            "The checkerboard squares must be at least 1 pixel wide!"
+        );
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let row_len = self.canvas.width() as usize * channels;
+        if row_len == 0 {
+            return;
         }
 
-        self.get_paste_coordinates_unchecked((self.last_pasted_index + 1) as u32)
+        // The container may be longer than the canvas, such as one given to `new_from_raw`, so only the canvas is painted.
+        let canvas: &mut [<P as Pixel>::Subpixel] = self.canvas.get_image_mut();
+        let canvas = &mut canvas[..row_len * self.canvas.height() as usize];
+        self.install(|| {
+            canvas
+                .par_chunks_exact_mut(row_len)
+                .enumerate()
+                .for_each(|(y, row)| {
+                    let square_y = y as u32 / square;
+                    for (x, pixel) in row.chunks_exact_mut(channels).enumerate() {
+                        let color = if (x as u32 / square + square_y).is_multiple_of(2) {
+                            &light
+                        } else {
+                            &dark
+                        };
+                        pixel.copy_from_slice(color.channels());
+                    }
+                })
+        });
     }
 
-    /// Removes an image from the canvas at the given index. Indices start at 0 and work left to right, top to bottom. Most of the time
-    /// you will not need to use this function, and rather, can use the `remove_image` method instead. This method is useful if you need
-    /// to manually manage a specific Container type that is not `Vec`.
-    ///
+    /// Returns the dimensions, (x, y), of the images being pasted to the canvas.
+    pub fn get_image_dimensions(&self) -> (u32, u32) {
+        self.image_dimensions
+    }
+
+    /// Returns whether an image of the given dimensions has to be resized before it can be pasted, which is the case when
+    /// they differ from the image dimensions of the merger. This allows deciding how to load an image before loading it.
     /// # Arguments
-    /// * `index` - The index of the image to remove.
-    /// * `container` - The container to use to replace the image. The container must be the same size as the image being removed,
-    /// thus, the container must be the same size as the image dimensions.
-    ///
-    /// # Returns
-    /// * `Some` - If the image was successfully removed.
-    /// * `None` - If the image could not be removed. This will happen if the container is not large enough to fit the image.
-    pub fn remove_image_raw(&mut self, index: u32, container: Container) -> Option<()> {
-        let offset_x = index % self.images_per_row;
-        let offset_y = index / self.images_per_row;
+    /// * `image_dimensions` - The dimensions, (x, y), of the image.
+    pub fn needs_resize(&self, image_dimensions: (u32, u32)) -> bool {
+        image_dimensions != self.image_dimensions
+    }
 
-        let x = offset_x * self.image_dimensions.0;
-        let y = offset_y * self.image_dimensions.1;
+    /// Returns the total number of images the canvas can hold, which is the `total_images` it was created with. Valid indices
+    /// are `0..capacity()`. When this is not a multiple of the images per row, the cells at the end of the last row are left
+    /// blank and are not valid indices. This is not to be confused with the capacity of the canvas' image buffer.
+    pub fn capacity(&self) -> u32 {
+        self.occupied.len() as u32
+    }
 
-        let black_image =
-            Image::new_from_raw(self.image_dimensions.0, self.image_dimensions.1, container);
+    /// Returns how many more images can be pushed before the canvas is full. Pushing past this will panic.
+    pub fn remaining_capacity(&self) -> u32 {
+        self.additional_space()
+    }
 
-        if let Some(black_image) = black_image {
-            paste(&self.canvas, &black_image, Point { x, y });
-            Some(())
-        } else {
-            None
-        }
+    /// Returns whether the canvas is full, in which case pushing another image will panic.
+    pub fn is_full(&self) -> bool {
+        self.additional_space() == 0
     }
-}
 
-impl<P> KnownSizeMerger<P, Vec<P::Subpixel>>
-where
-    P: Pixel + Sync,
-    <P as Pixel>::Subpixel: Sync,
-{
-    /// Constructs a new KnownSizeMerger. By default, this will create an underlying canvas with `Vec` as the container type. If you
-    /// need to use a different container type, you can use the `new_from_raw` method.
-    ///
+    /// Returns an iterator over the rectangles of every image that has been pushed to the canvas, in push order. Each item is
+    /// `(index, top_left, dimensions)`, where `top_left` is the location of the image on the canvas, including any padding.
+    /// This is useful for building image maps of the final canvas.
+    pub fn cell_rects(&self) -> impl Iterator<Item = (u32, Point, (u32, u32))> + '_ {
+        (0..self.occupied.len() as u32)
+            .filter(move |&index| self.occupied[index as usize])
+            .map(move |index| {
+                let (x, y) = self.get_paste_coordinates_unchecked(index);
+                (index, Point { x, y }, self.image_dimensions)
+            })
+    }
+
+    /// Returns the locations the next `count` pushes would paste their images at, in order, without changing the merger. This
+    /// is useful for inspecting the layout, or for scheduling the pastes yourself.
     /// # Arguments
-    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
-    /// * `images_per_row` - The number of images per row.
-    /// * `total_images` - The total number of images to be in the final canvas.
-    /// * `padding` - The padding between images, or None for no padding.
-    pub fn new(
-        image_dimensions: (u32, u32),
-        images_per_row: u32,
-        total_images: u32,
-        padding: Option<Padding>,
-    ) -> Self {
-        let total_rows = (total_images + images_per_row - 1) / images_per_row;
+    /// * `count` - The number of pushes to plan.
+    /// # Returns
+    /// The top left point of each planned image on the canvas, including any padding. This stops early if there is no more
+    /// space on the canvas, so it can hold fewer than `count` points.
+    pub fn plan(&self, count: u32) -> Vec<Point> {
+        self.next_empty_cells(count.min(self.additional_space()) as usize)
+            .into_iter()
+            .map(|index| {
+                let (x, y) = self.get_paste_coordinates_unchecked(index);
+                Point { x, y }
+            })
+            .collect()
+    }
 
-        let image_gaps_x = (images_per_row - 1) * padding.as_ref().map(|p| p.x).unwrap_or(0);
-        let image_gaps_y = (total_rows - 1) * padding.as_ref().map(|p| p.y).unwrap_or(0);
+    /// Returns a description of every occupied cell on the canvas, in the order of their cells. Each entry holds the index of
+    /// the cell and the rectangle of its image in canvas pixel coordinates, which is useful for generating image maps.
+    pub fn manifest(&self) -> Vec<CellInfo> {
+        self.cell_rects()
+            .map(|(index, loc, (width, height))| CellInfo {
+                index,
+                x: loc.x,
+                y: loc.y,
+                width,
+                height,
+            })
+            .collect()
+    }
 
-        let canvas = Image::new(
-            (image_dimensions.0 * images_per_row) + image_gaps_x,
-            (image_dimensions.1 * total_rows) + image_gaps_y,
-        );
+    /// Returns an iterator over copies of every image that has been pushed to the canvas, in the order of their cells. This is
+    /// useful for post-processing each image after merging.
+    pub fn iter(&self) -> impl Iterator<Item = BufferedImage<P>> + '_
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        self.cell_rects()
+            .map(move |(_, loc, dimensions)| crop(&self.canvas, loc, dimensions))
+    }
 
-        Self {
-            canvas: ImageCell::new(canvas),
-            image_dimensions,
-            num_images: 0,
-            images_per_row,
-            last_pasted_index: -1,
-            total_rows,
-            padding,
+    /// Returns a copy of one row of the grid as its own image, such as for showing the canvas one row at a time. The strip
+    /// spans from the left of the first image to the right of the last image in the row, including the padding between them,
+    /// and is as tall as the images.
+    /// # Arguments
+    /// * `row` - The row to copy, starting at 0 for the top row.
+    /// # Returns
+    /// * `Some` - The copied row.
+    /// * `None` - If the row is not on the canvas, or the merger was created with `with_placement` and so has no rows.
+    pub fn row_image(&self, row: u32) -> Option<BufferedImage<P>>
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        if self.placement.is_some() {
+            return None;
         }
+
+        let y = *self.row_offsets.get(row as usize)?;
+        let left = self.column_offsets[0];
+        let right = self.column_offsets[self.column_offsets.len() - 1] + self.image_dimensions.0;
+
+        Some(crop(
+            &self.canvas,
+            Point { x: left, y },
+            (right - left, self.image_dimensions.1),
+        ))
     }
 
-    /// Removes an image from the canvas at a given index. Indexing starts at 0 and works left to right, top to bottom.
+    /// Returns the index of the cell whose image contains the given point on the canvas. This is the inverse of finding
+    /// where an index is pasted, and is useful for mapping clicks on the final canvas back to the image that was pushed.
     /// # Arguments
-    /// * `index` - The index of the image to remove.
-    pub fn remove_image(&mut self, index: u32) {
-        let container: Vec<<P as Pixel>::Subpixel> = vec![
-            Zero::zero();
-            (self.image_dimensions.0 * self.image_dimensions.1 * <P as Pixel>::CHANNEL_COUNT as u32)
-                as usize
-        ];
+    /// * `x` - The x coordinate of the point on the canvas.
+    /// * `y` - The y coordinate of the point on the canvas.
+    /// # Returns
+    /// * `Some(index)` - The index of the cell containing the point.
+    /// * `None` - If the point is in the padding between images, in the margin, or outside of the canvas.
+    pub fn index_at(&self, x: u32, y: u32) -> Option<u32> {
+        if let Some(placement) = &self.placement {
+            // Images placed by a closure may overlap, in which case the highest index is on top.
+            let (width, height) = self.image_dimensions;
+            let capacity = self.capacity();
+            return (0..capacity).rev().find(|&index| {
+                let Point { x: left, y: top } = placement(index);
+                self.fits_on_canvas(left, top)
+                    && (left..left + width).contains(&x)
+                    && (top..top + height).contains(&y)
+            });
+        }
 
-        self.remove_image_raw(index, container).unwrap(); // Can always unwrap here because we know the buffer is the right size.
+        let edges = &self.edge_padding;
+        let padding = self.padding.unwrap_or(Point { x: 0, y: 0 });
+        let x = x.checked_sub(self.margin.x)?;
+        let y = y.checked_sub(self.margin.y)?;
 
-        let offset_x = index % self.images_per_row;
-        let offset_y = index / self.images_per_row;
+        // Finds which cell the coordinate falls in along one axis, if it lands on an image.
+        let axis = |coordinate: u32, size: u32, before: u32, after: u32, gap: u32, count: u32| {
+            let stride = size + before + after + gap;
+            let cell = coordinate / stride;
+            let within = coordinate % stride;
 
-        let x = offset_x * self.image_dimensions.0;
-        let y = offset_y * self.image_dimensions.1;
+            (cell < count && within >= before && within < before + size).then_some(cell)
+        };
 
-        let black_image: BufferedImage<P> =
-            Image::new(self.image_dimensions.0, self.image_dimensions.1);
+        let column = axis(
+            x,
+            self.image_dimensions.0,
+            edges.left,
+            edges.right,
+            padding.x,
+            self.images_per_row,
+        )?;
+        let row = axis(
+            y,
+            self.image_dimensions.1,
+            edges.top,
+            edges.bottom,
+            padding.y,
+            self.total_rows,
+        )?;
 
-        paste(&self.canvas, &black_image, Point { x, y });
+        // The blank cells at the end of the last row are not valid indices.
+        let index = row * self.images_per_row + column;
+        (index < self.capacity()).then_some(index)
     }
-}
 
-impl<P, Container> Merger<P, Container> for KnownSizeMerger<P, Container>
-where
-    P: Pixel + Sync,
-    <P as Pixel>::Subpixel: Sync,
-    Container: DerefMut<Target = [P::Subpixel]> + Sync,
-{
-    fn get_canvas(&self) -> &Image<P, image::ImageBuffer<P, Container>> {
-        &self.canvas
-    }
+    /// Encodes an animated GIF that shows each image on the canvas as one frame. Frames follow the order of the cells, which is
+    /// the order the images were pushed in unless they were pushed to specific cells with `push_at`.
+    /// # Arguments
+    /// * `frame_delay_ms` - How long each frame is shown for, in milliseconds.
+    /// # Returns
+    /// The encoded GIF, or the [image::ImageError](image::ImageError) that occurred while encoding.
+    pub fn encode_animation(&self, frame_delay_ms: u16) -> image::ImageResult<Vec<u8>>
+    where
+        <P as Pixel>::Subpixel: Send,
+        ImageBuffer<P, Vec<P::Subpixel>>: ConvertBuffer<RgbaImage>,
+    {
+        let delay = Delay::from_numer_denom_ms(frame_delay_ms as u32, 1);
+        let frames: Vec<Frame> = self.install(|| {
+            self.cell_rects()
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .map(|(_, loc, dimensions)| {
+                    let cell = crop(&self.canvas, loc, dimensions).into_buffer();
+                    Frame::from_parts(cell.convert(), 0, 0, delay)
+                })
+                .collect()
+        });
 
-    fn into_canvas(self) -> Image<P, image::ImageBuffer<P, Container>> {
-        self.canvas.into_inner()
+        let mut buffer = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut buffer);
+            encoder.encode_frames(frames)?;
+        }
+
+        Ok(buffer)
     }
 
-    fn push(&mut self, image: &Image<P, image::ImageBuffer<P, Container>>) {
-        let (x, y) = self.get_next_paste_coordinates();
+    /// Draws a rectangular outline of the given color and thickness around every image that has been pushed to the canvas.
+    /// If the merger has padding, the outline is drawn in the gutters surrounding each image. Otherwise, there is no room
+    /// between images, so the outline is drawn over the outermost pixels of each image instead. Outlines are clipped to the
+    /// bounds of the canvas.
+    /// # Arguments
+    /// * `color` - The color of the outline.
+    /// * `thickness` - The thickness of the outline, in pixels.
+    pub fn draw_cell_borders(&mut self, color: P, thickness: u32) {
+        let has_gutters = self.padding.is_some() || self.edge_padding != EdgePadding::default();
+        let (width, height) = self.image_dimensions;
 
-        paste(&self.canvas, image, Point { x, y });
+        // Each border is the area between an outer and an inner rectangle, given as (x0, y0, x1, y1).
+        type Rect = (u32, u32, u32, u32);
+        let borders: Vec<(Rect, Rect)> = self
+            .cell_rects()
+            .map(|(_, top_left, _)| {
+                let image_rect = (
+                    top_left.x,
+                    top_left.y,
+                    top_left.x + width,
+                    top_left.y + height,
+                );
+                let (x0, y0, x1, y1) = image_rect;
 
-        self.last_pasted_index += 1;
-        self.num_images += 1;
+                if has_gutters {
+                    // The outline surrounds the image.
+                    let outer = (
+                        x0.saturating_sub(thickness),
+                        y0.saturating_sub(thickness),
+                        x1 + thickness,
+                        y1 + thickness,
+                    );
+                    (outer, image_rect)
+                } else {
+                    // The outline covers the outermost pixels of the image.
+                    let inner_x0 = (x0 + thickness).min(x1);
+                    let inner_y0 = (y0 + thickness).min(y1);
+                    let inner = (
+                        inner_x0,
+                        inner_y0,
+                        x1.saturating_sub(thickness).max(inner_x0),
+                        y1.saturating_sub(thickness).max(inner_y0),
+                    );
+                    (image_rect, inner)
+                }
+            })
+            .collect();
+
+        let canvas = self.canvas.get_image_mut();
+        let (canvas_width, canvas_height) = canvas.dimensions();
+        let mut fill = |x0: u32, y0: u32, x1: u32, y1: u32| {
+            for y in y0..y1.min(canvas_height) {
+                for x in x0..x1.min(canvas_width) {
+                    canvas.put_pixel(x, y, color);
+                }
+            }
+        };
+
+        for ((x0, y0, x1, y1), (inner_x0, inner_y0, inner_x1, inner_y1)) in borders {
+            fill(x0, y0, x1, inner_y0); // Top
+            fill(x0, inner_y1, x1, y1); // Bottom
+            fill(x0, inner_y0, inner_x0, inner_y1); // Left
+            fill(inner_x1, inner_y0, x1, inner_y1); // Right
+        }
     }
 
-    fn bulk_push(&mut self, images: &[&Image<P, image::ImageBuffer<P, Container>>]) {
-        // If we can't fit all the images we need to panic.
-        if self.additional_space() < images.len() as u32 {
-            // TODO: Maybe only take as many images as we can fit?
-            panic!("There is not enough space on the canvas to fit all the requested images.");
+    /// Zeroes every pixel of the canvas that is not part of a pasted image, such as the padding between images, the edge
+    /// padding around them, the margin, the unused space at the end of the last row, and the cells that do not hold an image
+    /// yet. A canvas built on a reused container by `new_from_raw` can hold stale data in any of these, so zeroing them makes
+    /// the finished canvas, and any hash of it, depend only on the pushed images.
+    pub fn zero_padding(&mut self)
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        let (width, height) = self.image_dimensions;
+        let mut cells: Vec<Point> = (0..self.capacity())
+            .filter(|&index| self.occupied[index as usize])
+            .map(|index| {
+                let (x, y) = self.get_paste_coordinates_unchecked(index);
+                Point { x, y }
+            })
+            .collect();
+        cells.sort_unstable_by_key(|cell| (cell.y, cell.x));
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let row_len = self.canvas.width() as usize * channels;
+        if row_len == 0 {
+            return;
         }
 
-        (0..images.len()).into_par_iter().for_each(|index| {
-            let image = images[index];
+        // The container may be longer than the canvas, such as one given to `new_from_raw`, so only the canvas is zeroed.
+        let canvas: &mut [<P as Pixel>::Subpixel] = self.canvas.get_image_mut();
+        let canvas = &mut canvas[..row_len * self.canvas.height() as usize];
 
-            // The image coordinates can easily be calculated by using the last_pasted_index
-            // and making the calculations ourselves.
-            let offset_index = (index as i32 + self.last_pasted_index + 1) as u32;
+        self.install(|| {
+            canvas
+                .par_chunks_exact_mut(row_len)
+                .enumerate()
+                .for_each(|(y, row)| {
+                    // The filled cells crossing this row are the ones starting at most `height - 1` rows above it.
+                    let y = y as u32;
+                    let first = cells.partition_point(|cell| cell.y + height <= y);
+                    let last = cells.partition_point(|cell| cell.y <= y);
+                    let mut spans: Vec<(usize, usize)> = cells[first..last]
+                        .iter()
+                        .map(|cell| (cell.x as usize, (cell.x + width) as usize))
+                        .collect();
+                    spans.sort_unstable();
 
-            let (x, y) = self.get_paste_coordinates_unchecked(offset_index);
-            paste(&self.canvas, image, Point { x, y });
+                    // Zero the gaps between the filled cells, then everything after the last one.
+                    let mut x = 0;
+                    for (start, end) in spans {
+                        if start > x {
+                            row[x * channels..start * channels].fill(Zero::zero());
+                        }
+                        x = x.max(end);
+                    }
+                    row[x * channels..].fill(Zero::zero());
+                })
         });
-
-        self.last_pasted_index += images.len() as i32;
-        self.num_images += images.len() as u32;
     }
-}
 
-impl<P> ResizableMerger<P> for KnownSizeMerger<P, Vec<<P as Pixel>::Subpixel>>
-where
-    P: Pixel + Sync + Send,
-    <P as Pixel>::Subpixel: Sync + Send,
-{
-    fn push_resized(&mut self, image: &BufferedImage<P>) {
+    /// Fills every empty cell after the last pushed image with the given color, including the blank cells at the end of the last
+    /// row when the total images are not a multiple of the images per row. This marks where there is no image on the final
+    /// canvas. It can be called at any time, as pushed images are pasted over the fill, and calling it again only repaints the
+    /// cells that are still empty. Cells filled out of order, such as by `push_at`, are left as they are.
+    /// # Arguments
+    /// * `color` - The color to fill the empty cells with.
+    pub fn fill_empty_cells(&mut self, color: P)
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        // A placement only places the images it was given, so it has no blank cells past them.
+        let cells = match self.placement {
+            Some(_) => self.capacity(),
+            None => self.images_per_row * self.total_rows,
+        };
+        let start = (self.last_pasted_index + 1) as u32;
         let (width, height) = self.image_dimensions;
-        let resized = resize_nearest_neighbor(image, width, height);
-        self.push(&resized);
-    }
+        let fill = color.channels();
+        let channels = fill.len();
 
-    fn bulk_push_resized(&mut self, images: &[&BufferedImage<P>]) {
-        // Resize all the images in parallel then push them
-        let resized_images: Vec<BufferedImage<P>> = images
-            .into_par_iter()
-            .map(|image| {
-                let (width, height) = self.image_dimensions;
-                return resize_nearest_neighbor(image, width, height);
-            })
+        let capacity = self.capacity();
+        let indices: Vec<u32> = (start..cells)
+            .filter(|&index| index >= capacity || !self.occupied[index as usize])
             .collect();
+        let fill_one = |&index: &u32| {
+            let (x, y) = self.get_paste_coordinates_unchecked(index);
 
-        // Convert Vec<T> to [&T] for the bulk push method
-        let resized_images_ref: Vec<&BufferedImage<P>> = resized_images.iter().collect();
+            // SAFETY: The indices are distinct, and cells are only filled in parallel when none of them overlap.
+            let mut region = unsafe { self.canvas.request_region(x, y, width, height) };
+            for row in 0..height {
+                for pixel in region.row_mut(row).chunks_exact_mut(channels) {
+                    pixel.copy_from_slice(fill);
+                }
+            }
+        };
 
-        self.bulk_push(&resized_images_ref);
+        // Grid cells never overlap, but a placement may put images on top of each other.
+        let overlapping = self.placement.is_some() && {
+            let locations: Vec<Point> = indices
+                .iter()
+                .map(|&index| {
+                    let (x, y) = self.get_paste_coordinates_unchecked(index);
+                    Point { x, y }
+                })
+                .collect();
+            self.any_overlap(&locations)
+        };
+        if overlapping {
+            indices.iter().for_each(fill_one);
+        } else {
+            self.install(|| indices.par_iter().for_each(fill_one));
+        }
+    }
+
+    /// Returns the number of empty cells after the last pasted image, which is how many more images can be pushed. Cells
+    /// filled out of order, such as by `push_at`, are not counted, as pushes skip over them.
+    fn additional_space(&self) -> u32 {
+        let start = (self.last_pasted_index + 1) as usize;
+        self.occupied[start..]
+            .iter()
+            .filter(|&&occupied| !occupied)
+            .count() as u32
+    }
+
+    /// Returns the indices of the next `count` empty cells after the last pasted image, in order. Cells filled out of order,
+    /// such as by `push_at`, are skipped so that pushing never overwrites them.
+    /// # Panics
+    /// This function will panic if there are fewer than `count` empty cells left.
+    fn next_empty_cells(&self, count: usize) -> Vec<u32> {
+        let start = (self.last_pasted_index + 1) as u32;
+        let cells: Vec<u32> = (start..self.capacity())
+            .filter(|&index| !self.occupied[index as usize])
+            .take(count)
+            .collect();
+        if cells.len() < count {
+            // TODO: Maybe only take as many images as we can fit?
+            panic!("There is not enough space on the canvas to fit all the requested images.");
+        }
+
+        cells
+    }
+
+    /// Marks the given cells, as returned by `next_empty_cells`, as holding an image and moves the last pasted index to the
+    /// last of them.
+    fn fill_cells(&mut self, cells: &[u32]) {
+        for &index in cells {
+            self.mark_occupied(index..index + 1);
+        }
+        if let Some(&last) = cells.last() {
+            self.last_pasted_index = last as i32;
+        }
+    }
+
+    /// Marks the cells in the given range as holding an image, counting the ones that were previously empty.
+    fn mark_occupied(&mut self, indices: Range<u32>) {
+        for occupied in &mut self.occupied[indices.start as usize..indices.end as usize] {
+            if !*occupied {
+                *occupied = true;
+                self.num_images += 1;
+            }
+        }
+    }
+
+    /// Returns whether any two images pasted at the given locations would overlap. Locations are bucketed by image size, so
+    /// only images in neighbouring buckets have to be compared.
+    fn any_overlap(&self, locations: &[Point]) -> bool {
+        let (width, height) = self.image_dimensions;
+        let mut buckets: HashMap<(u32, u32), Vec<Point>> = HashMap::new();
+        for location in locations {
+            let bucket = (location.x / width, location.y / height);
+            for bucket_x in bucket.0.saturating_sub(1)..=bucket.0.saturating_add(1) {
+                for bucket_y in bucket.1.saturating_sub(1)..=bucket.1.saturating_add(1) {
+                    let Some(others) = buckets.get(&(bucket_x, bucket_y)) else {
+                        continue;
+                    };
+                    if others.iter().any(|other| {
+                        location.x.abs_diff(other.x) < width
+                            && location.y.abs_diff(other.y) < height
+                    }) {
+                        return true;
+                    }
+                }
+            }
+            buckets.entry(bucket).or_default().push(*location);
+        }
+
+        false
+    }
+
+    /// Returns whether an image pasted at the given location lies entirely on the canvas.
+    fn fits_on_canvas(&self, x: u32, y: u32) -> bool {
+        let (width, height) = self.canvas.dimensions();
+        let fits_x = x
+            .checked_add(self.image_dimensions.0)
+            .is_some_and(|end| end <= width);
+        let fits_y = y
+            .checked_add(self.image_dimensions.1)
+            .is_some_and(|end| end <= height);
+
+        fits_x && fits_y
+    }
+
+    fn get_paste_coordinates_unchecked(&self, index: u32) -> (u32, u32) {
+        if let Some(placement) = &self.placement {
+            let Point { x, y } = placement(index);
+            assert!(
+                self.fits_on_canvas(x, y),
+                "The placement put image {index} at ({x}, {y}), outside of the canvas!"
+            );
+
+            return (x, y);
+        }
+
+        let column = (index % self.images_per_row) as usize;
+        let row = (index / self.images_per_row) as usize;
+
+        (self.column_offsets[column], self.row_offsets[row])
+    }
+
+    /// Computes the coordinate images in each column and row are pasted at, so that finding where an image goes is two
+    /// lookups instead of recomputing the padding each time. This must be called whenever the layout changes.
+    fn cache_offsets(&mut self) {
+        // The coordinate of each of the `count` cells along one axis.
+        let offsets = |count: u32, size: u32, before: u32, after: u32, gap: u32, margin: u32| {
+            let stride = size + before + after + gap;
+            (0..count)
+                .map(|cell| cell * stride + before + margin)
+                .collect()
+        };
+
+        let edges = &self.edge_padding;
+        let padding = self.padding.unwrap_or(Point { x: 0, y: 0 });
+        self.column_offsets = offsets(
+            self.images_per_row,
+            self.image_dimensions.0,
+            edges.left,
+            edges.right,
+            padding.x,
+            self.margin.x,
+        );
+        self.row_offsets = offsets(
+            self.total_rows,
+            self.image_dimensions.1,
+            edges.top,
+            edges.bottom,
+            padding.y,
+            self.margin.y,
+        );
+    }
+
+    /// Same as `cache_offsets`, but consumes and returns the merger for use while constructing it.
+    fn with_cached_offsets(mut self) -> Self {
+        self.cache_offsets();
+        self
+    }
+
+    /// Returns the coordinates the next pushed image is pasted at, skipping over any cells filled out of order. The last
+    /// pasted index is moved to just before that cell, so callers only need to increment it once the image is pasted.
+    fn get_next_paste_coordinates(&mut self) -> (u32, u32) {
+        let start = (self.last_pasted_index + 1) as u32;
+        let index = (start..self.capacity())
+            .find(|&index| !self.occupied[index as usize])
+            .expect("No more space on the canvas!");
+        self.last_pasted_index = index as i32 - 1;
+
+        self.get_paste_coordinates_unchecked(index)
+    }
+
+    /// Checks that every image in a batch matches the image dimensions of the merger, in parallel, so that nothing is pasted
+    /// when one of them does not.
+    fn check_dimensions<SourceContainer>(
+        &self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let expected = self.image_dimensions;
+        let mismatch = self.install(|| {
+            images
+                .par_iter()
+                .position_first(|image| image.dimensions() != expected)
+        });
+
+        match mismatch {
+            Some(index) => Err(MergerError::DimensionMismatch {
+                index: Some(index as u32),
+                expected,
+                got: images[index].dimensions(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Same as `bulk_push`, but returns an error instead of panicking if an image does not match the image dimensions of the
+    /// merger. Every image is checked before any are pasted, so the canvas is left untouched on error.
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas.
+    /// # Returns
+    /// * `Ok` - If the images were pasted.
+    /// * `Err(MergerError::DimensionMismatch)` - If an image does not match the image dimensions of the merger. The `index` of
+    ///   the error is the position of the first such image in `images`.
+    /// # Panics
+    /// This function will panic if there is not enough space on the canvas to fit all the images.
+    pub fn try_bulk_push<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        self.check_dimensions(images)?;
+        self.paste_batch(images, |_| {});
+
+        Ok(())
+    }
+
+    /// Same as `bulk_push`, but skips checking that every image matches the image dimensions of the merger. This saves a pass
+    /// over the images when they are already known to be the right size, such as when they were all made by the same resize.
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas.
+    /// # Safety
+    /// Every image must match the image dimensions of the merger. Images are pasted in parallel, so a larger image would be
+    /// written over the cells of its neighbours while they are being pasted.
+    /// # Panics
+    /// This function will panic if there is not enough space on the canvas to fit all the images.
+    pub unsafe fn bulk_push_unchecked<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+    ) where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        self.paste_batch(images, |_| {});
+    }
+
+    /// Same as `bulk_push`, but calls `on_progress` each time an image has been pasted onto the canvas. The callback receives the
+    /// number of images pasted so far, which increases by one with each call, and is called from the worker threads doing the
+    /// pasting, so it may be called concurrently and out of order.
+    ///
+    /// The resulting canvas does not depend on the number of threads or how the work is scheduled. Images are pasted in
+    /// parallel only when their cells are disjoint. If a placement makes cells overlap, the images are pasted in order, so
+    /// later images are drawn over earlier ones just like with repeated calls to `push`.
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas.
+    /// * `on_progress` - The callback to call with the number of completed images.
+    /// # Panics
+    /// This function will panic if there is not enough space on the canvas to fit all the images, or if any image does not
+    /// match the image dimensions of the merger.
+    pub fn bulk_push_with_progress<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+        on_progress: impl Fn(usize) + Sync,
+    ) where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        self.check_dimensions(images)
+            .expect("Every image must match the image dimensions of the merger!");
+        self.paste_batch(images, on_progress);
+    }
+
+    /// Pastes a batch of images into the next empty cells, calling `on_progress` as each one is pasted. The images are not
+    /// checked against the image dimensions of the merger, so callers must do so first.
+    fn paste_batch<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+        on_progress: impl Fn(usize) + Sync,
+    ) where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        // If we can't fit all the images we need to panic.
+        let cells = self.next_empty_cells(images.len());
+
+        // Every location is worked out before pasting, so nothing shared is updated from the parallel region.
+        let locations: Vec<Point> = cells
+            .iter()
+            .map(|&index| {
+                let (x, y) = self.get_paste_coordinates_unchecked(index);
+                Point { x, y }
+            })
+            .collect();
+
+        // Grid cells never overlap, so only placements need checking outside of debug builds.
+        let overlapping =
+            (self.placement.is_some() || cfg!(debug_assertions)) && self.any_overlap(&locations);
+        debug_assert!(
+            self.placement.is_some() || !overlapping,
+            "The cells of a grid must never overlap!"
+        );
+
+        let completed = AtomicUsize::new(0);
+        let paste_one = |index: usize| {
+            self.paste_image(images[index], locations[index]);
+            on_progress(completed.fetch_add(1, Ordering::Relaxed) + 1);
+        };
+        if overlapping {
+            // The order images are pasted in decides which one ends up on top, so it must not be left to the scheduler.
+            (0..images.len()).for_each(paste_one);
+        } else {
+            self.install(|| (0..images.len()).into_par_iter().for_each(paste_one));
+        }
+
+        self.fill_cells(&cells);
+    }
+
+    /// Same as `bulk_push`, but carries on past images that fail validation instead of panicking. Every valid image is pasted
+    /// into its cell, while the cell of each invalid image is skipped and left blank, so the remaining images still land
+    /// where `bulk_push` would have put them.
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas.
+    /// # Returns
+    /// The position in `images` and the error of every image that was not pasted, in the order they were given. This is
+    /// empty if every image was pasted.
+    /// # Panics
+    /// This function will panic if there is not enough space on the canvas to fit all the images.
+    pub fn bulk_push_collecting<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+    ) -> Vec<(usize, MergerError)>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let cells = self.next_empty_cells(images.len());
+        let expected = self.image_dimensions;
+        let errors: Vec<(usize, MergerError)> = images
+            .iter()
+            .enumerate()
+            .filter(|(_, image)| image.dimensions() != expected)
+            .map(|(index, image)| {
+                let error = MergerError::DimensionMismatch {
+                    index: Some(index as u32),
+                    expected,
+                    got: image.dimensions(),
+                };
+                (index, error)
+            })
+            .collect();
+
+        let valid: Vec<(usize, Point)> = (0..images.len())
+            .filter(|&index| images[index].dimensions() == expected)
+            .map(|index| {
+                let (x, y) = self.get_paste_coordinates_unchecked(cells[index]);
+                (index, Point { x, y })
+            })
+            .collect();
+
+        let locations: Vec<Point> = valid.iter().map(|&(_, loc)| loc).collect();
+        if self.placement.is_some() && self.any_overlap(&locations) {
+            valid
+                .iter()
+                .for_each(|&(index, loc)| self.paste_image(images[index], loc));
+        } else {
+            self.install(|| {
+                valid
+                    .par_iter()
+                    .for_each(|&(index, loc)| self.paste_image(images[index], loc))
+            });
+        }
+
+        // The cells of invalid images are left empty, but later pushes still continue after them.
+        let filled: Vec<u32> = valid.iter().map(|&(index, _)| cells[index]).collect();
+        self.fill_cells(&filled);
+        if let Some(&last) = cells.last() {
+            self.last_pasted_index = last as i32;
+        }
+
+        errors
+    }
+
+    /// Same as `bulk_push`, but avoids re-pasting identical images. Each image is hashed once, and only the first of any
+    /// identical images is pasted from its source. Every other copy is duplicated from the already pasted cell on the canvas
+    /// instead. This is useful when many of the images are the same, such as placeholders. The final canvas is identical to
+    /// the one produced by `bulk_push`.
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas.
+    /// # Panics
+    /// This function will panic if there is not enough space on the canvas to fit all the images, or if any image does not
+    /// match the image dimensions of the merger.
+    pub fn bulk_push_deduped<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+    ) where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+        <P as Pixel>::Subpixel: Hash + Eq,
+    {
+        let cells = self.next_empty_cells(images.len());
+        self.check_dimensions(images)
+            .expect("Every image must match the image dimensions of the merger!");
+
+        // Overlapping cells can be pasted over by a later image before a duplicate is copied out of them, so every image is
+        // pasted in order instead, the same way `bulk_push` does.
+        if self.placement.is_some() {
+            let locations: Vec<Point> = cells
+                .iter()
+                .map(|&index| {
+                    let (x, y) = self.get_paste_coordinates_unchecked(index);
+                    Point { x, y }
+                })
+                .collect();
+            if self.any_overlap(&locations) {
+                for (image, &loc) in images.iter().zip(&locations) {
+                    self.paste_image(image, loc);
+                }
+                self.fill_cells(&cells);
+                return;
+            }
+        }
+
+        let hashes: Vec<u64> = self.install(|| {
+            images
+                .par_iter()
+                .map(|image| {
+                    let mut hasher = DefaultHasher::new();
+                    image.dimensions().hash(&mut hasher);
+                    let pixels: &[<P as Pixel>::Subpixel] = image;
+                    pixels.hash(&mut hasher);
+                    hasher.finish()
+                })
+                .collect()
+        });
+
+        // Maps every image to the first image before it with the same contents, if there is one. Candidates sharing a hash
+        // are compared in full so a hash collision can never duplicate the wrong image.
+        let identical = |a: usize, b: usize| {
+            let (first, second): (&[<P as Pixel>::Subpixel], &[<P as Pixel>::Subpixel]) =
+                (images[a], images[b]);
+            images[a].dimensions() == images[b].dimensions() && first == second
+        };
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+        let sources: Vec<Option<usize>> = (0..images.len())
+            .map(|index| {
+                let candidates = seen.entry(hashes[index]).or_default();
+                let source = candidates
+                    .iter()
+                    .copied()
+                    .find(|&candidate| identical(candidate, index));
+                if source.is_none() {
+                    candidates.push(index);
+                }
+                source
+            })
+            .collect();
+
+        self.install(|| {
+            (0..images.len())
+                .into_par_iter()
+                .filter(|&index| sources[index].is_none())
+                .for_each(|index| {
+                    let (x, y) = self.get_paste_coordinates_unchecked(cells[index]);
+                    self.paste_image(images[index], Point { x, y });
+                })
+        });
+
+        // Copy every duplicate row by row from the cell its first occurrence was pasted into.
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let canvas_width = self.canvas.width() as usize;
+        for (index, source) in sources.iter().enumerate() {
+            let Some(source) = source else {
+                continue;
+            };
+
+            let (from_x, from_y) = self.get_paste_coordinates_unchecked(cells[*source]);
+            let (to_x, to_y) = self.get_paste_coordinates_unchecked(cells[index]);
+            let (width, height) = images[index].dimensions();
+            let row_len = width as usize * channels;
+
+            let canvas: &mut [<P as Pixel>::Subpixel] = self.canvas.get_image_mut();
+            for y in 0..height as usize {
+                let from = ((from_y as usize + y) * canvas_width + from_x as usize) * channels;
+                let to = ((to_y as usize + y) * canvas_width + to_x as usize) * channels;
+                canvas.copy_within(from..from + row_len, to);
+            }
+        }
+
+        self.fill_cells(&cells);
+    }
+
+    /// Pastes the same image into the next `count` empty cells of the canvas, in parallel. The final canvas is identical to the one
+    /// produced by `bulk_push` with `count` references to the image, without needing to allocate them.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas.
+    /// * `count` - The number of times to push the image.
+    /// # Returns
+    /// * `Ok` - If the image was pasted `count` times.
+    /// * `Err(MergerError::DimensionMismatch)` - If the image does not match the image dimensions of the merger.
+    /// * `Err(MergerError::IndexOutOfBounds)` - If there is not enough space on the canvas for `count` images. The `index` is
+    ///   the first cell past the end of the canvas.
+    ///
+    /// Nothing is pasted when an error is returned.
+    pub fn push_repeated<SourceContainer>(
+        &mut self,
+        image: &Image<P, image::ImageBuffer<P, SourceContainer>>,
+        count: u32,
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        if image.dimensions() != self.image_dimensions {
+            return Err(MergerError::DimensionMismatch {
+                index: None,
+                expected: self.image_dimensions,
+                got: image.dimensions(),
+            });
+        }
+        if self.additional_space() < count {
+            let capacity = self.capacity();
+            return Err(MergerError::IndexOutOfBounds {
+                index: capacity,
+                capacity,
+            });
+        }
+
+        let cells = self.next_empty_cells(count as usize);
+        let locations: Vec<Point> = cells
+            .iter()
+            .map(|&index| {
+                let (x, y) = self.get_paste_coordinates_unchecked(index);
+                Point { x, y }
+            })
+            .collect();
+
+        // Every copy is the same image, so the order only matters where placements partly overlap one another.
+        if self.placement.is_some() && self.any_overlap(&locations) {
+            locations
+                .iter()
+                .for_each(|&loc| self.paste_image(image, loc));
+        } else {
+            self.install(|| {
+                locations
+                    .par_iter()
+                    .for_each(|&loc| self.paste_image(image, loc))
+            });
+        }
+
+        self.fill_cells(&cells);
+
+        Ok(())
+    }
+
+    /// Fills every remaining empty cell of the canvas with an image made by `factory`, which is given the index of the cell. Each
+    /// image is made on the same worker thread that pastes it, so expensive work such as decoding is spread across the thread
+    /// pool along with the pasting, and only the images currently being pasted are held in memory.
+    /// # Arguments
+    /// * `factory` - Makes the image for the cell at the given index.
+    /// # Panics
+    /// This function will panic if `factory` returns an image that does not match the image dimensions of the merger.
+    pub fn fill_with<F>(&mut self, factory: F)
+    where
+        F: Fn(u32) -> BufferedImage<P> + Sync,
+    {
+        let cells = self.next_empty_cells(self.additional_space() as usize);
+        let locations: Vec<Point> = cells
+            .iter()
+            .map(|&index| {
+                let (x, y) = self.get_paste_coordinates_unchecked(index);
+                Point { x, y }
+            })
+            .collect();
+
+        let paste_one = |position: usize| {
+            let index = cells[position];
+            let image = factory(index);
+            assert_eq!(
+                image.dimensions(),
+                self.image_dimensions,
+                "The factory made an image for cell {index} that does not match the image dimensions of the merger!"
+            );
+            self.paste_image(&image, locations[position]);
+        };
+        if self.placement.is_some() && self.any_overlap(&locations) {
+            // The order images are pasted in decides which one ends up on top, so it must not be left to the scheduler.
+            (0..cells.len()).for_each(paste_one);
+        } else {
+            self.install(|| (0..cells.len()).into_par_iter().for_each(paste_one));
+        }
+
+        self.fill_cells(&cells);
+    }
+
+    /// Same as `push`, but returns the index of the cell the image was pasted into. This saves callers from tracking where
+    /// each image landed themselves.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas.
+    /// # Returns
+    /// The index of the cell the image was pasted into.
+    /// # Panics
+    /// This function will panic if there is no more space on the canvas.
+    pub fn push_indexed<SourceContainer>(
+        &mut self,
+        image: &Image<P, image::ImageBuffer<P, SourceContainer>>,
+    ) -> u32
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        self.push(image);
+        self.last_pasted_index as u32
+    }
+
+    /// Same as `bulk_push`, but returns the range of cell indices the images were pasted into, in the order they were given.
+    /// Pushes skip over cells filled out of order, such as by `push_at`, so any such cells between the first and last image
+    /// are part of the range too.
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas.
+    /// # Returns
+    /// The range of the cell indices the images were pasted into.
+    /// # Panics
+    /// This function will panic if there is not enough space on the canvas to fit all the images.
+    pub fn bulk_push_indexed<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+    ) -> Range<u32>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let start = (self.last_pasted_index + 1) as u32;
+        let cells = self.next_empty_cells(images.len());
+        self.bulk_push(images);
+        match (cells.first(), cells.last()) {
+            (Some(&first), Some(&last)) => first..last + 1,
+            _ => start..start,
+        }
+    }
+
+    /// Skips over the next `n` cells without pasting anything into them, so the next pushed image lands `n` cells later. The
+    /// skipped cells are left as they are, which is the background color for a fresh canvas, and do not count towards the
+    /// number of images on the canvas.
+    /// # Arguments
+    /// * `n` - The number of cells to skip.
+    /// # Panics
+    /// This function will panic if there are fewer than `n` cells left on the canvas.
+    pub fn push_skip(&mut self, n: u32) {
+        if self.capacity() - ((self.last_pasted_index + 1) as u32) < n {
+            panic!("There is not enough space on the canvas to skip the requested cells.");
+        }
+
+        self.last_pasted_index += n as i32;
+    }
+
+    /// Pushes an image onto the canvas at the given index, rather than after the last pushed image. If the cell already holds an
+    /// image, it is overwritten, which allows updating a single image in place. Indices start at 0 and work left to right, top
+    /// to bottom. Later pushes skip over the cell, so the image is never overwritten by them.
+    /// # Arguments
+    /// * `index` - The index of the cell to paste the image into.
+    /// * `image` - The image to paste.
+    /// # Returns
+    /// * `Ok` - If the image was pasted.
+    /// * `Err(MergerError::IndexOutOfBounds)` - If the index is not a cell on the canvas.
+    /// * `Err(MergerError::DimensionMismatch)` - If the image does not match the image dimensions of the merger.
+    pub fn push_at<SourceContainer>(
+        &mut self,
+        index: u32,
+        image: &Image<P, image::ImageBuffer<P, SourceContainer>>,
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let capacity = self.capacity();
+        if index >= capacity {
+            return Err(MergerError::IndexOutOfBounds { index, capacity });
+        }
+        if image.dimensions() != self.image_dimensions {
+            return Err(MergerError::DimensionMismatch {
+                index: None,
+                expected: self.image_dimensions,
+                got: image.dimensions(),
+            });
+        }
+
+        let (x, y) = self.get_paste_coordinates_unchecked(index);
+        self.paste_image(image, Point { x, y });
+        self.mark_occupied(index..index + 1);
+
+        Ok(())
+    }
+
+    /// Pastes each image into its own given cell in parallel, rather than after the last pushed image. This is useful for
+    /// filling the canvas out of order, such as when images finish loading at different times. Every index is validated before
+    /// anything is pasted, so the canvas is left untouched on error. Like `push_at`, any image already in a cell is overwritten.
+    /// # Arguments
+    /// * `placements` - The index of the cell to paste each image into, paired with the image.
+    /// # Returns
+    /// * `Ok` - If every image was pasted.
+    /// * `Err(MergerError::IndexOutOfBounds)` - If an index is not a cell on the canvas.
+    /// * `Err(MergerError::DuplicateIndex)` - If more than one image was given for the same cell.
+    /// * `Err(MergerError::DimensionMismatch)` - If an image does not match the image dimensions of the merger. The `index` is
+    ///   the position of the image in `placements`.
+    #[allow(clippy::type_complexity)]
+    pub fn scatter_push<SourceContainer>(
+        &mut self,
+        placements: &[(u32, &Image<P, image::ImageBuffer<P, SourceContainer>>)],
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let capacity = self.capacity();
+        let mut claimed = vec![false; capacity as usize];
+        for (position, &(index, image)) in placements.iter().enumerate() {
+            if index >= capacity {
+                return Err(MergerError::IndexOutOfBounds { index, capacity });
+            }
+            if std::mem::replace(&mut claimed[index as usize], true) {
+                return Err(MergerError::DuplicateIndex { index });
+            }
+            if image.dimensions() != self.image_dimensions {
+                return Err(MergerError::DimensionMismatch {
+                    index: Some(position as u32),
+                    expected: self.image_dimensions,
+                    got: image.dimensions(),
+                });
+            }
+        }
+
+        let locations: Vec<Point> = placements
+            .iter()
+            .map(|&(index, _)| {
+                let (x, y) = self.get_paste_coordinates_unchecked(index);
+                Point { x, y }
+            })
+            .collect();
+
+        let paste_one =
+            |position: usize| self.paste_image(placements[position].1, locations[position]);
+        if self.placement.is_some() && self.any_overlap(&locations) {
+            // The order images are pasted in decides which one ends up on top, so it must not be left to the scheduler.
+            (0..placements.len()).for_each(paste_one);
+        } else {
+            self.install(|| (0..placements.len()).into_par_iter().for_each(paste_one));
+        }
+
+        for &(index, _) in placements {
+            self.mark_occupied(index..index + 1);
+        }
+
+        Ok(())
+    }
+
+    /// Pastes a whole pre-rendered canvas, such as one from another merger, onto this canvas at an absolute location. Unlike
+    /// pushing, this is not tied to a cell, which allows stitching sub-grids built separately into a larger canvas. The
+    /// pasted canvas does not count towards the number of images on this canvas.
+    /// # Arguments
+    /// * `other` - The canvas to paste.
+    /// * `at` - The location of the top left of `other` on this canvas.
+    /// # Returns
+    /// * `Ok` - If the canvas was pasted.
+    /// * `Err(MergerError::OutOfCanvas)` - If `other` would extend past the edge of this canvas.
+    pub fn paste_canvas(
+        &mut self,
+        other: &Image<P, image::ImageBuffer<P, Container>>,
+        at: Point,
+    ) -> Result<(), MergerError> {
+        let (width, height) = self.canvas.dimensions();
+        let fits_x =
+            at.x.checked_add(other.width())
+                .is_some_and(|end| end <= width);
+        let fits_y =
+            at.y.checked_add(other.height())
+                .is_some_and(|end| end <= height);
+        if !fits_x || !fits_y {
+            return Err(MergerError::OutOfCanvas {
+                at: (at.x, at.y),
+                dimensions: other.dimensions(),
+                canvas: (width, height),
+            });
+        }
+
+        self.paste_image(other, at);
+
+        Ok(())
+    }
+
+    /// Pushes an image onto the canvas through a mask. Each mask value controls how much of the image replaces the canvas
+    /// beneath it, where 0 keeps the canvas and 255 fully replaces it with the image.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas.
+    /// * `mask` - The mask to paste the image through. It must be the same size as the image.
+    /// # Returns
+    /// * `Ok` - If the image was pasted.
+    /// * `Err(MergerError::DimensionMismatch)` - If the image does not match the image dimensions of the merger, or the mask
+    ///   is not the same size as the image.
+    /// # Panics
+    /// This function will panic if there is no more space on the canvas.
+    pub fn push_masked<SourceContainer, MaskContainer>(
+        &mut self,
+        image: &Image<P, image::ImageBuffer<P, SourceContainer>>,
+        mask: &Image<Luma<u8>, ImageBuffer<Luma<u8>, MaskContainer>>,
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+        MaskContainer: DerefMut<Target = [u8]> + Sync,
+    {
+        if image.dimensions() != self.image_dimensions {
+            return Err(MergerError::DimensionMismatch {
+                index: None,
+                expected: self.image_dimensions,
+                got: image.dimensions(),
+            });
+        }
+        if mask.dimensions() != image.dimensions() {
+            return Err(MergerError::DimensionMismatch {
+                index: None,
+                expected: image.dimensions(),
+                got: mask.dimensions(),
+            });
+        }
+
+        let (x, y) = self.get_next_paste_coordinates();
+        self.install(|| paste_masked(&self.canvas, image, mask, Point { x, y }));
+
+        self.last_pasted_index += 1;
+        let index = self.last_pasted_index as u32;
+        self.mark_occupied(index..index + 1);
+
+        Ok(())
+    }
+
+    /// Removes an image from the canvas at the given index. Indices start at 0 and work left to right, top to bottom. Most of the time
+    /// you will not need to use this function, and rather, can use the `remove_image` method instead. This method is useful if you need
+    /// to manually manage a specific Container type that is not `Vec`.
+    ///
+    /// # Arguments
+    /// * `index` - The index of the image to remove.
+    /// * `container` - The container to use to replace the image. The container must be the same size as the image being removed,
+    ///   thus, the container must be the same size as the image dimensions.
+    ///
+    /// # Returns
+    /// * `Some` - If the image was successfully removed.
+    /// * `None` - If the image could not be removed. This will happen if the index is not less than `capacity()`, or if the
+    ///   container is not large enough to fit the image.
+    pub fn remove_image_raw(&mut self, index: u32, container: Container) -> Option<()> {
+        if index >= self.capacity() {
+            return None;
+        }
+
+        let (x, y) = self.get_paste_coordinates_unchecked(index);
+
+        let black_image =
+            Image::new_from_raw(self.image_dimensions.0, self.image_dimensions.1, container);
+
+        if let Some(black_image) = black_image {
+            self.paste_image(&black_image, Point { x, y });
+
+            if let Some(occupied) = self.occupied.get_mut(index as usize) {
+                if *occupied {
+                    *occupied = false;
+                    self.num_images -= 1;
+                }
+            }
+            Some(())
+        } else {
+            None
+        }
+    }
+}
+
+impl<P> KnownSizeMerger<P, Vec<P::Subpixel>>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+{
+    /// Constructs a new KnownSizeMerger. By default, this will create an underlying canvas with `Vec` as the container type. If you
+    /// need to use a different container type, you can use the `new_from_raw` method.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `padding` - The padding between images, or None for no padding.
+    ///
+    /// # Panics
+    /// This function will panic if the image dimensions, images per row, or total images are 0, or if the canvas would be too
+    /// large. Use `try_new` to handle these cases instead.
+    pub fn new(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_images: u32,
+        padding: Option<Padding>,
+    ) -> Self {
+        Self::try_new(image_dimensions, images_per_row, total_images, padding)
+            .expect("The merger could not be created with the given parameters!")
+    }
+
+    /// Computes how many bytes the canvas of a merger built by `new` with the same parameters would take up, without
+    /// allocating it. This is useful for deciding whether a grid fits in memory or should be backed by a memory-mapped file
+    /// through `new_from_raw` instead. The result matches `memory_footprint` once the merger is built.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `padding` - The padding between images, or None for no padding.
+    ///
+    /// # Returns
+    /// The size of the canvas in bytes. This is 0 if the parameters do not describe a valid canvas, and saturates at
+    /// `usize::MAX` if the canvas could never be allocated.
+    pub fn estimate_footprint(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_images: u32,
+        padding: Option<Padding>,
+    ) -> usize {
+        if validate_layout(image_dimensions, images_per_row, total_images).is_err() {
+            return 0;
+        }
+
+        let total_rows = total_images.div_ceil(images_per_row);
+        canvas_dimensions(
+            image_dimensions,
+            images_per_row,
+            total_rows,
+            padding.as_ref(),
+            &EdgePadding::default(),
+            &Point { x: 0, y: 0 },
+        )
+        .and_then(|(width, height)| {
+            (width as usize)
+                .checked_mul(height as usize)?
+                .checked_mul(<P as Pixel>::CHANNEL_COUNT as usize)?
+                .checked_mul(std::mem::size_of::<P::Subpixel>())
+        })
+        .unwrap_or(usize::MAX)
+    }
+
+    /// Same as `new`, but returns an error instead of panicking if the parameters do not describe a valid canvas.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `padding` - The padding between images, or None for no padding.
+    ///
+    /// # Returns
+    /// * `Ok` - If the merger was successfully created.
+    /// * `Err(MergerError::InvalidLayout)` - If the image dimensions, images per row, or total images are 0, or the canvas
+    ///   would be larger than `u32::MAX` pixels along either axis.
+    pub fn try_new(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_images: u32,
+        padding: Option<Padding>,
+    ) -> Result<Self, MergerError> {
+        let builder = KnownSizeMergerBuilder::new()
+            .image_dimensions(image_dimensions)
+            .images_per_row(images_per_row)
+            .total_images(total_images);
+
+        match padding {
+            Some(padding) => builder.padding(padding).try_build(),
+            None => builder.try_build(),
+        }
+    }
+
+    /// Constructs a new KnownSizeMerger that lays its images out in a single strip, either a single row or a single column
+    /// depending on the orientation.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `total_images` - The total number of images to be in the strip.
+    /// * `padding` - The padding between images, or None for no padding. Only the axis along the strip is used.
+    /// * `orientation` - Whether the strip is horizontal or vertical.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{KnownSizeMerger, Merger, Orientation, Rgb};
+    ///
+    /// let merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new_strip((100, 100), 5, None, Orientation::Horizontal);
+    /// assert_eq!(merger.get_canvas().dimensions(), (500, 100));
+    /// ```
+    pub fn new_strip(
+        image_dimensions: (u32, u32),
+        total_images: u32,
+        padding: Option<Padding>,
+        orientation: Orientation,
+    ) -> Self {
+        let images_per_row = match orientation {
+            Orientation::Horizontal => total_images,
+            Orientation::Vertical => 1,
+        };
+
+        Self::new(image_dimensions, images_per_row, total_images, padding)
+    }
+
+    /// Constructs a new KnownSizeMerger with the given gutters around its images. This is the same as `new`, but accepts
+    /// either a [Padding](Padding) between images or an [EdgePadding](EdgePadding) around each side of every image.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `gutters` - The gutters around the images.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{EdgePadding, KnownSizeMerger, Rgb};
+    ///
+    /// let edges = EdgePadding { top: 20, right: 0, bottom: 5, left: 5 };
+    /// let merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new_with_gutters((100, 100), 5, 10, edges);
+    /// ```
+    pub fn new_with_gutters(
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_images: u32,
+        gutters: impl Into<Gutters>,
+    ) -> Self {
+        KnownSizeMergerBuilder::new()
+            .image_dimensions(image_dimensions)
+            .images_per_row(images_per_row)
+            .total_images(total_images)
+            .gutters(gutters)
+            .build()
+    }
+
+    /// Constructs a new KnownSizeMerger from a sprite sheet, an image that already holds a grid of uniformly sized images. The
+    /// sheet is sliced into its images, left to right, top to bottom, which are then pushed onto the new canvas in that order.
+    /// This is useful for rearranging an existing sheet, such as into a different number of images per row.
+    ///
+    /// # Arguments
+    /// * `sheet` - The sprite sheet to slice.
+    /// * `tile_width` - The width of each image on the sheet.
+    /// * `tile_height` - The height of each image on the sheet.
+    /// * `images_per_row` - The number of images per row on the new canvas.
+    /// * `padding` - The padding between images, both on the sheet and on the new canvas, or None for no padding.
+    ///
+    /// # Panics
+    /// This function will panic if the sheet does not hold at least one image of the given size.
+    pub fn from_sheet<Container>(
+        sheet: &Image<P, ImageBuffer<P, Container>>,
+        tile_width: u32,
+        tile_height: u32,
+        images_per_row: u32,
+        padding: Option<Padding>,
+    ) -> Self
+    where
+        P: Send,
+        <P as Pixel>::Subpixel: Send,
+        Container: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let gap = padding.unwrap_or(Point { x: 0, y: 0 });
+        let stride_x = tile_width + gap.x;
+        let stride_y = tile_height + gap.y;
+
+        // The last image on each axis has no gap after it.
+        let columns = (sheet.width() + gap.x).checked_div(stride_x).unwrap_or(0);
+        let rows = (sheet.height() + gap.y).checked_div(stride_y).unwrap_or(0);
+
+        let tiles: Vec<BufferedImage<P>> = (0..columns * rows)
+            .into_par_iter()
+            .map(|index| {
+                let loc = Point {
+                    x: (index % columns) * stride_x,
+                    y: (index / columns) * stride_y,
+                };
+                crop(sheet, loc, (tile_width, tile_height))
+            })
+            .collect();
+
+        let mut merger = Self::new(
+            (tile_width, tile_height),
+            images_per_row,
+            columns * rows,
+            padding,
+        );
+
+        // Convert Vec<T> to [&T] for the bulk push method
+        let tiles_ref: Vec<&BufferedImage<P>> = tiles.iter().collect();
+        merger.bulk_push(&tiles_ref);
+
+        merger
+    }
+
+    /// Constructs a new KnownSizeMerger by stacking row strips, such as those returned by `row_image`, from top to bottom.
+    /// This allows each row of a grid to be built independently, such as on its own thread, and assembled afterwards. The
+    /// strips are copied onto the canvas in parallel, and every cell of every strip is counted as holding an image.
+    ///
+    /// # Arguments
+    /// * `rows` - The row strips, from the top row to the bottom. Every strip must have the same dimensions.
+    /// * `images_per_row` - The number of images in each strip.
+    /// * `padding` - The padding between images, both within each strip and between the rows, or None for no padding.
+    ///
+    /// # Returns
+    /// * `Some` - If the merger was successfully created.
+    /// * `None` - If there are no rows, the strips differ in size, or the width of the strips does not fit exactly
+    ///   `images_per_row` images with the padding between them.
+    pub fn from_rows(
+        rows: Vec<BufferedImage<P>>,
+        images_per_row: u32,
+        padding: Option<Padding>,
+    ) -> Option<Self>
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        let (strip_width, height) = rows.first()?.dimensions();
+        if rows
+            .iter()
+            .any(|row| row.dimensions() != (strip_width, height))
+        {
+            return None;
+        }
+
+        // The last image in each strip has no padding after it.
+        let gap = padding.unwrap_or(Point { x: 0, y: 0 });
+        let images_width =
+            strip_width.checked_sub(images_per_row.checked_sub(1)?.checked_mul(gap.x)?)?;
+        if images_width % images_per_row != 0 {
+            return None;
+        }
+
+        let total_images = (rows.len() as u32).checked_mul(images_per_row)?;
+        let mut merger = Self::try_new(
+            (images_width / images_per_row, height),
+            images_per_row,
+            total_images,
+            padding,
+        )
+        .ok()?;
+
+        let x = merger.column_offsets[0];
+        merger.install(|| {
+            rows.par_iter().enumerate().for_each(|(row, strip)| {
+                merger.paste_image(
+                    strip,
+                    Point {
+                        x,
+                        y: merger.row_offsets[row],
+                    },
+                )
+            })
+        });
+        merger.mark_occupied(0..total_images);
+        merger.last_pasted_index = total_images as i32 - 1;
+
+        Some(merger)
+    }
+
+    /// Constructs a new KnownSizeMerger on top of an existing canvas, such as a background image, rather than a blank one. The
+    /// grid starts at the top left of the canvas, and anything not covered by a pushed image keeps its existing content. The
+    /// canvas may be larger than the grid.
+    ///
+    /// # Arguments
+    /// * `canvas` - The canvas to paste the images onto.
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `images_per_row` - The number of images per row.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `padding` - The padding between images, or None for no padding.
+    ///
+    /// # Returns
+    /// * `Some` - If the merger was successfully created.
+    /// * `None` - If the canvas is too small to fit the grid, or the layout has no cells or empty images.
+    pub fn from_canvas(
+        canvas: BufferedImage<P>,
+        image_dimensions: (u32, u32),
+        images_per_row: u32,
+        total_images: u32,
+        padding: Option<Padding>,
+    ) -> Option<Self> {
+        validate_layout(image_dimensions, images_per_row, total_images).ok()?;
+
+        let total_rows = total_images.div_ceil(images_per_row);
+        let (width, height) = canvas_dimensions(
+            image_dimensions,
+            images_per_row,
+            total_rows,
+            padding.as_ref(),
+            &EdgePadding::default(),
+            &Point { x: 0, y: 0 },
+        )?;
+        if canvas.width() < width || canvas.height() < height {
+            return None;
+        }
+
+        Some(
+            KnownSizeMerger {
+                canvas: ImageCell::new(canvas),
+                image_dimensions,
+                num_images: 0,
+                images_per_row,
+                last_pasted_index: -1,
+                total_rows,
+                occupied: vec![false; total_images as usize],
+                padding,
+                edge_padding: EdgePadding::default(),
+                margin: Point { x: 0, y: 0 },
+                background: None,
+                thread_pool: None,
+                sequential_paste_threshold: SEQUENTIAL_PASTE_THRESHOLD,
+                placement: None,
+                column_offsets: Vec::new(),
+                row_offsets: Vec::new(),
+            }
+            .with_cached_offsets(),
+        )
+    }
+
+    /// Constructs a new KnownSizeMerger that pastes each image wherever the given closure places it, instead of in a grid. This
+    /// allows arbitrary layouts, such as spirals or masonry. Padding does not apply, as the closure decides the exact location
+    /// of every image.
+    ///
+    /// # Arguments
+    /// * `image_dimensions` - The dimensions of the images being pasted (images must be a uniform size)
+    /// * `canvas_size` - The dimensions of the canvas.
+    /// * `total_images` - The total number of images to be in the final canvas.
+    /// * `placement` - Returns the location of the top left of the image at the given index. Every location is checked
+    ///   against the canvas when the image is pasted.
+    ///
+    /// # Panics
+    /// This function will panic if the image dimensions or total images are 0. Pushing will panic if the placement puts an
+    /// image even partially outside of the canvas.
+    ///
+    /// # Example
+    /// ```
+    /// use image_merger::{KnownSizeMerger, Merger, Image, Point, Rgb};
+    ///
+    /// // Place the images along a diagonal.
+    /// let mut merger: KnownSizeMerger<Rgb<u8>, _> =
+    ///     KnownSizeMerger::with_placement((10, 10), (30, 30), 3, |index| Point { x: index * 10, y: index * 10 });
+    /// merger.push(&Image::new(10, 10));
+    /// ```
+    pub fn with_placement(
+        image_dimensions: (u32, u32),
+        canvas_size: (u32, u32),
+        total_images: u32,
+        placement: impl Fn(u32) -> Point + Send + Sync + 'static,
+    ) -> Self {
+        validate_layout(image_dimensions, 1, total_images)
+            .expect("The merger could not be created with the given parameters!");
+
+        KnownSizeMerger {
+            canvas: ImageCell::new(Image::new(canvas_size.0, canvas_size.1)),
+            image_dimensions,
+            num_images: 0,
+            images_per_row: 1,
+            last_pasted_index: -1,
+            total_rows: total_images,
+            occupied: vec![false; total_images as usize],
+            padding: None,
+            edge_padding: EdgePadding::default(),
+            margin: Point { x: 0, y: 0 },
+            background: None,
+            thread_pool: None,
+            sequential_paste_threshold: SEQUENTIAL_PASTE_THRESHOLD,
+            placement: Some(Arc::new(placement)),
+            column_offsets: Vec::new(),
+            row_offsets: Vec::new(),
+        }
+        .with_cached_offsets()
+    }
+
+    /// Returns a [KnownSizeMergerBuilder](KnownSizeMergerBuilder) that can be used to construct a KnownSizeMerger.
+    pub fn builder() -> KnownSizeMergerBuilder<P> {
+        KnownSizeMergerBuilder::new()
+    }
+
+    /// Pushes images onto the canvas one at a time as they are pulled from an iterator. Unlike `bulk_push`, the images never
+    /// need to be held in memory all at once, only the one currently being pasted is.
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas.
+    /// # Returns
+    /// * `Ok` - If every image was pasted.
+    /// * `Err(MergerError::IndexOutOfBounds)` - If the iterator yields more images than there is space for. The canvas is
+    ///   filled, and only the first image that did not fit is pulled from the iterator.
+    pub fn push_iter<I>(&mut self, images: I) -> Result<(), MergerError>
+    where
+        I: IntoIterator<Item = BufferedImage<P>>,
+    {
+        let mut images = images.into_iter();
+        for _ in 0..self.additional_space() {
+            match images.next() {
+                Some(image) => self.push(&image),
+                None => return Ok(()),
+            }
+        }
+
+        // The canvas is full, so any further image is one too many.
+        let capacity = self.capacity();
+        match images.next() {
+            Some(_) => Err(MergerError::IndexOutOfBounds {
+                index: capacity,
+                capacity,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Writes the layout, progress, and canvas of the merger so it can be restored later with `load_state`. This allows
+    /// long-running jobs to checkpoint their progress and resume after being interrupted. The background color and thread
+    /// pool are not saved.
+    ///
+    /// The format is a 4 byte magic number and a version byte, followed by the layout, progress, and canvas size as
+    /// little-endian integers, the occupancy of every cell, and finally the raw bytes of the canvas. The canvas size is saved
+    /// separately from the layout, as a canvas from `from_canvas` may be larger than the grid.
+    /// # Arguments
+    /// * `writer` - Where to write the state to.
+    /// # Returns
+    /// The [io::Error](std::io::Error) that occurred while writing, if any. Mergers created with `with_placement` can not be
+    /// saved, as their placement can not be written out.
+    pub fn save_state<W: Write>(&self, mut writer: W) -> io::Result<()>
+    where
+        [P::Subpixel]: EncodableLayout,
+    {
+        if self.placement.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Mergers with a custom placement can not be saved!",
+            ));
+        }
+
+        let padding = self.padding.unwrap_or(Point { x: 0, y: 0 });
+        let edges = &self.edge_padding;
+        let header = [
+            self.image_dimensions.0,
+            self.image_dimensions.1,
+            self.images_per_row,
+            self.capacity(),
+            self.num_images,
+            self.last_pasted_index as u32,
+            padding.x,
+            padding.y,
+            edges.top,
+            edges.right,
+            edges.bottom,
+            edges.left,
+            self.margin.x,
+            self.margin.y,
+            self.canvas.width(),
+            self.canvas.height(),
+        ];
+
+        writer.write_all(STATE_MAGIC)?;
+        writer.write_all(&[
+            STATE_VERSION,
+            self.padding.is_some() as u8,
+            cfg!(target_endian = "little") as u8,
+            std::mem::size_of::<P::Subpixel>() as u8,
+            <P as Pixel>::CHANNEL_COUNT,
+        ])?;
+        for value in header {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+
+        let occupied: Vec<u8> = self.occupied.iter().map(|&cell| cell as u8).collect();
+        writer.write_all(&occupied)?;
+
+        writer.write_all(self.as_subpixels().as_bytes())?;
+        writer.flush()
+    }
+
+    /// Restores a merger from state written by `save_state`. The restored merger continues where the saved one left off, so
+    /// further images can be pushed onto it.
+    /// # Arguments
+    /// * `reader` - Where to read the state from.
+    /// # Returns
+    /// The restored merger, or an [io::Error](std::io::Error) if the state could not be read or was not written by
+    /// `save_state` for this pixel type on a machine with the same endianness.
+    pub fn load_state<R: Read>(mut reader: R) -> io::Result<Self>
+    where
+        [P::Subpixel]: EncodableLayout,
+    {
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != STATE_MAGIC {
+            return Err(invalid_state("Not a merger state file!"));
+        }
+        if read_u8(&mut reader)? != STATE_VERSION {
+            return Err(invalid_state("Unsupported merger state version!"));
+        }
+
+        let has_padding = read_u8(&mut reader)? != 0;
+        let little_endian = read_u8(&mut reader)? != 0;
+        let subpixel_size = read_u8(&mut reader)? as usize;
+        let channels = read_u8(&mut reader)?;
+        if little_endian != cfg!(target_endian = "little")
+            || subpixel_size != std::mem::size_of::<P::Subpixel>()
+            || channels != <P as Pixel>::CHANNEL_COUNT
+        {
+            return Err(invalid_state(
+                "The merger state was saved with a different pixel type or endianness!",
+            ));
+        }
+
+        let mut header = [0; 16];
+        for value in header.iter_mut() {
+            *value = read_u32(&mut reader)?;
+        }
+        let [image_width, image_height, images_per_row, total_images, num_images, last_pasted_index, padding_x, padding_y, top, right, bottom, left, margin_x, margin_y, canvas_width, canvas_height] =
+            header;
+
+        let padding = has_padding.then_some(Point {
+            x: padding_x,
+            y: padding_y,
+        });
+        let edge_padding = EdgePadding {
+            top,
+            right,
+            bottom,
+            left,
+        };
+        let margin = Point {
+            x: margin_x,
+            y: margin_y,
+        };
+        let image_dimensions = (image_width, image_height);
+
+        validate_layout(image_dimensions, images_per_row, total_images)
+            .map_err(|_| invalid_state("The merger state has an invalid layout!"))?;
+        let total_rows = total_images.div_ceil(images_per_row);
+
+        // The last pasted index is written as -1 before anything has been pasted.
+        let last_pasted_index = last_pasted_index as i32;
+        if last_pasted_index < -1 || last_pasted_index >= total_images as i32 {
+            return Err(invalid_state(
+                "The merger state has a last pasted index past the end of the canvas!",
+            ));
+        }
+
+        let mut occupied = Vec::new();
+        (&mut reader)
+            .take(total_images as u64)
+            .read_to_end(&mut occupied)?;
+        if occupied.len() != total_images as usize {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        if occupied.iter().filter(|&&cell| cell != 0).count() != num_images as usize {
+            return Err(invalid_state(
+                "The merger state has a different number of images than occupied cells!",
+            ));
+        }
+
+        let (width, height) = canvas_dimensions(
+            image_dimensions,
+            images_per_row,
+            total_rows,
+            padding.as_ref(),
+            &edge_padding,
+            &margin,
+        )
+        .ok_or_else(|| invalid_state("The merger state has an invalid canvas size!"))?;
+        if canvas_width < width || canvas_height < height {
+            return Err(invalid_state(
+                "The merger state has a canvas too small to fit its grid!",
+            ));
+        }
+        let (width, height) = (canvas_width, canvas_height);
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(<P as Pixel>::CHANNEL_COUNT as usize))
+            .ok_or_else(|| invalid_state("The merger state has an invalid canvas size!"))?;
+
+        let mut canvas: Vec<P::Subpixel> = Vec::new();
+        while canvas.len() < len {
+            let start = canvas.len();
+            canvas.resize(start + (len - start).min(STATE_READ_CHUNK), Zero::zero());
+            let chunk = &mut canvas[start..];
+
+            // SAFETY: `EncodableLayout` is only implemented for slices of plain numeric types, for which every bit pattern is
+            // a valid value, so the canvas can be filled byte by byte.
+            let bytes = unsafe {
+                std::slice::from_raw_parts_mut(
+                    chunk.as_mut_ptr() as *mut u8,
+                    std::mem::size_of_val(chunk),
+                )
+            };
+            reader.read_exact(bytes)?;
+        }
+
+        let canvas = Image::new_from_raw(width, height, canvas)
+            .ok_or_else(|| invalid_state("The merger state has an invalid canvas size!"))?;
+
+        Ok(Self {
+            canvas: ImageCell::new(canvas),
+            image_dimensions,
+            num_images,
+            images_per_row,
+            last_pasted_index,
+            total_rows,
+            occupied: occupied.into_iter().map(|cell| cell != 0).collect(),
+            padding,
+            edge_padding,
+            margin,
+            background: None,
+            thread_pool: None,
+            sequential_paste_threshold: SEQUENTIAL_PASTE_THRESHOLD,
+            placement: None,
+            column_offsets: Vec::new(),
+            row_offsets: Vec::new(),
+        }
+        .with_cached_offsets())
+    }
+
+    /// Removes an image from the canvas at a given index, replacing it with the background color if one was set. Indexing
+    /// starts at 0 and works left to right, top to bottom.
+    /// # Arguments
+    /// * `index` - The index of the image to remove.
+    /// # Returns
+    /// * `Ok` - If the image was removed.
+    /// * `Err(MergerError::IndexOutOfBounds)` - If the index is not less than `capacity()`.
+    pub fn remove_image(&mut self, index: u32) -> Result<(), MergerError> {
+        let capacity = self.capacity();
+        if index >= capacity {
+            return Err(MergerError::IndexOutOfBounds { index, capacity });
+        }
+
+        let (width, height) = self.image_dimensions;
+
+        // Removed images are replaced with the background the canvas was created with, if any.
+        let container: Vec<<P as Pixel>::Subpixel> = match self.background {
+            Some(background) => BufferedImage::new_from_pixel(width, height, background)
+                .into_buffer()
+                .into_raw(),
+            None => {
+                vec![
+                    Zero::zero();
+                    width as usize * height as usize * <P as Pixel>::CHANNEL_COUNT as usize
+                ]
+            }
+        };
+
+        self.remove_image_raw(index, container).unwrap(); // Can always unwrap here because we know the buffer is the right size.
+        Ok(())
+    }
+
+    /// Removes the images at the given indices from the canvas in parallel, replacing them with the background color if one
+    /// was set. This avoids allocating a fresh buffer for every cell like repeated calls to `remove_image` would. Every index is
+    /// validated before anything is removed, so the canvas is left untouched on error.
+    /// # Arguments
+    /// * `indices` - The indices of the images to remove.
+    /// # Returns
+    /// * `Ok` - If every image was removed.
+    /// * `Err(MergerError::IndexOutOfBounds)` - If an index is not less than `capacity()`.
+    /// * `Err(MergerError::DuplicateIndex)` - If an index was given more than once.
+    pub fn remove_images(&mut self, indices: &[u32]) -> Result<(), MergerError>
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        let capacity = self.capacity();
+        let mut claimed = vec![false; capacity as usize];
+        for &index in indices {
+            if index >= capacity {
+                return Err(MergerError::IndexOutOfBounds { index, capacity });
+            }
+            if std::mem::replace(&mut claimed[index as usize], true) {
+                return Err(MergerError::DuplicateIndex { index });
+            }
+        }
+
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let fill: Vec<<P as Pixel>::Subpixel> = match self.background {
+            Some(background) => background.channels().to_vec(),
+            None => vec![Zero::zero(); channels],
+        };
+        let (width, height) = self.image_dimensions;
+        let clear = |&index: &u32| {
+            let (x, y) = self.get_paste_coordinates_unchecked(index);
+
+            // SAFETY: The indices are distinct, and cells are only cleared in parallel when none of them overlap.
+            let mut region = unsafe { self.canvas.request_region(x, y, width, height) };
+            for row in 0..height {
+                for pixel in region.row_mut(row).chunks_exact_mut(channels) {
+                    pixel.copy_from_slice(&fill);
+                }
+            }
+        };
+
+        // Grid cells never overlap, but a placement may put images on top of each other.
+        let overlapping = self.placement.is_some() && {
+            let locations: Vec<Point> = indices
+                .iter()
+                .map(|&index| {
+                    let (x, y) = self.get_paste_coordinates_unchecked(index);
+                    Point { x, y }
+                })
+                .collect();
+            self.any_overlap(&locations)
+        };
+        if overlapping {
+            indices.iter().for_each(clear);
+        } else {
+            self.install(|| indices.par_iter().for_each(clear));
+        }
+
+        let cleared = indices
+            .iter()
+            .filter(|&&index| std::mem::replace(&mut self.occupied[index as usize], false))
+            .count() as u32;
+        self.num_images -= cleared;
+
+        Ok(())
+    }
+
+    /// Grows the canvas so it can hold at least `additional_images` more images, adding rows to the bottom. Images already on
+    /// the canvas keep their positions, so pushing continues where it left off. New space is filled with the background color
+    /// if one was set. Mergers created with `with_placement` keep their canvas size, and only accept more images. A canvas
+    /// from `from_canvas` that is larger than the grid keeps its extra space, and only grows once the grid outgrows it.
+    /// # Arguments
+    /// * `additional_images` - The number of extra images the canvas must be able to hold.
+    /// # Panics
+    /// This function will panic if the grown canvas would be larger than `u32::MAX` pixels along either axis.
+    pub fn grow(&mut self, additional_images: u32)
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        let capacity = self
+            .capacity()
+            .checked_add(additional_images)
+            .expect("The canvas is too large to grow by the requested number of images!");
+        let total_rows = capacity.div_ceil(self.images_per_row);
+        self.occupied.resize(capacity as usize, false);
+
+        // New images may fit in the blank cells of the last row. Otherwise, when a placement decides where the new images go,
+        // the canvas itself stays the same size.
+        if total_rows == self.total_rows || self.placement.is_some() {
+            self.total_rows = total_rows;
+            self.cache_offsets();
+            return;
+        }
+
+        let (width, height) = canvas_dimensions(
+            self.image_dimensions,
+            self.images_per_row,
+            total_rows,
+            self.padding.as_ref(),
+            &self.edge_padding,
+            &self.margin,
+        )
+        .expect("The canvas is too large to grow by the requested number of images!");
+        let (old_width, old_height) = self.canvas.dimensions();
+        let (width, height) = (width.max(old_width), height.max(old_height));
+        let mut canvas = match self.background {
+            Some(background) => BufferedImage::new_from_pixel(width, height, background),
+            None => BufferedImage::new(width, height),
+        };
+
+        // Every row keeps its layout, so the old canvas is exactly the top left of the new one.
+        let channels = <P as Pixel>::CHANNEL_COUNT as usize;
+        let old_row_len = old_width as usize * channels;
+        let row_len = width as usize * channels;
+        let old: &[<P as Pixel>::Subpixel] = self.canvas.get_image_mut();
+        let old = &old[..old_row_len * old_height as usize];
+        let new: &mut [<P as Pixel>::Subpixel] = &mut canvas;
+        if old_row_len > 0 {
+            self.install(|| {
+                new.par_chunks_mut(row_len)
+                    .zip(old.par_chunks(old_row_len))
+                    .for_each(|(to, from)| to[..old_row_len].copy_from_slice(from))
+            });
+        }
+
+        self.canvas = ImageCell::new(canvas);
+        self.total_rows = total_rows;
+        self.cache_offsets();
+    }
+
+    /// Pushes an image with a different pixel type onto the canvas, converting it into the canvas' pixel type first. For example,
+    /// this allows an `Rgb<u8>` image to be pushed onto an `Rgba<u8>` canvas, in which case the pasted pixels are fully opaque.
+    /// # Arguments
+    /// * `image` - The image to convert and push onto the canvas.
+    pub fn push_converted<SP, SC>(&mut self, image: &Image<SP, ImageBuffer<SP, SC>>)
+    where
+        SP: Pixel,
+        SC: DerefMut<Target = [SP::Subpixel]>,
+        ImageBuffer<SP, SC>: ConvertBuffer<ImageBuffer<P, Vec<P::Subpixel>>>,
+    {
+        let converted: BufferedImage<P> = Image::from(image.convert());
+        self.push(&converted);
+    }
+
+    /// Pushes an image onto the canvas after fading it by the given opacity, optionally towards a tint color. This is useful
+    /// for showing hover or disabled states. With an opacity of 1.0 and no tint, this is the same as `push`.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas.
+    /// * `opacity` - How much of the original image to keep, from 0.0 to 1.0.
+    /// * `tint_color` - The color to fade the image towards, or None to fade it towards zero.
+    pub fn push_tinted(&mut self, image: &BufferedImage<P>, opacity: f32, tint_color: Option<P>)
+    where
+        P: Send,
+        <P as Pixel>::Subpixel: Send,
+    {
+        let tinted = self.install(|| tint(image, opacity, tint_color));
+        self.push(&tinted);
+    }
+
+    /// Pushes an image onto the canvas after flipping or rotating it. This is useful for building sprite atlases with mirrored
+    /// variants of the same image.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas.
+    /// * `transform` - The transform to apply to the image before pasting it.
+    /// # Returns
+    /// * `Ok` - If the image was pasted.
+    /// * `Err(MergerError::DimensionMismatch)` - If the transformed image does not match the image dimensions of the merger.
+    ///   This can happen when a non-square image is rotated by 90 or 270 degrees.
+    /// # Panics
+    /// This function will panic if there is no more space on the canvas.
+    pub fn push_transformed(
+        &mut self,
+        image: &BufferedImage<P>,
+        transform_kind: Transform,
+    ) -> Result<(), MergerError>
+    where
+        P: Send,
+        <P as Pixel>::Subpixel: Send,
+    {
+        let dimensions = transform_kind.transformed_dimensions(image.dimensions());
+        if dimensions != self.image_dimensions {
+            return Err(MergerError::DimensionMismatch {
+                index: None,
+                expected: self.image_dimensions,
+                got: dimensions,
+            });
+        }
+
+        let transformed = self.install(|| transform(image, transform_kind));
+        self.push(&transformed);
+
+        Ok(())
+    }
+
+    /// Pushes an image onto the canvas after scaling it up by a whole number with [resize_integer](crate::raw::resize_integer),
+    /// so every source pixel becomes a `factor` by `factor` block. This is useful for pixel art, where resizing by a ratio
+    /// could round a pixel into the wrong place.
+    /// # Arguments
+    /// * `image` - The image to scale and push onto the canvas.
+    /// * `factor` - How many times larger the pasted image is along each axis.
+    /// # Returns
+    /// * `Ok` - If the image was pasted.
+    /// * `Err(MergerError::DimensionMismatch)` - If the scaled image does not match the image dimensions of the merger.
+    /// # Panics
+    /// This function will panic if `factor` is 0, or if there is no more space on the canvas.
+    pub fn push_scaled(&mut self, image: &BufferedImage<P>, factor: u32) -> Result<(), MergerError>
+    where
+        P: Send,
+        <P as Pixel>::Subpixel: Send,
+    {
+        assert!(factor > 0, "An image can not be scaled by a factor of 0!");
+        let (width, height) = image.dimensions();
+        let dimensions = (width.saturating_mul(factor), height.saturating_mul(factor));
+        if dimensions != self.image_dimensions {
+            return Err(MergerError::DimensionMismatch {
+                index: None,
+                expected: self.image_dimensions,
+                got: dimensions,
+            });
+        }
+
+        let scaled = self.install(|| resize_integer(image, factor));
+        self.push(&scaled);
+
+        Ok(())
+    }
+
+    /// Pushes an image with straight alpha onto the canvas, blending it over whatever is already in its cell, such as the
+    /// background color, rather than replacing it.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas.
+    /// * `color_space` - The color space to blend in. `ColorSpace::Linear` avoids the dark fringes of blending in sRGB.
+    /// # Panics
+    /// This function will panic if there is no more space on the canvas, or if the image does not match the image dimensions
+    /// of the merger.
+    pub fn push_blended<SourceContainer>(
+        &mut self,
+        image: &Image<P, image::ImageBuffer<P, SourceContainer>>,
+        color_space: ColorSpace,
+    ) where
+        P: AlphaPixel,
+        SourceContainer: DerefMut<Target = [u8]> + Sync,
+    {
+        assert_eq!(
+            image.dimensions(),
+            self.image_dimensions,
+            "The image must match the image dimensions of the merger!"
+        );
+        let (x, y) = self.get_next_paste_coordinates();
+        self.install(|| paste_blended(&self.canvas, image, Point { x, y }, color_space));
+
+        self.last_pasted_index += 1;
+        let index = self.last_pasted_index as u32;
+        self.mark_occupied(index..index + 1);
+    }
+
+    /// Same as `push_blended`, but pushes many images at once, blending them onto their cells in parallel. Images that are
+    /// fully transparent would leave their cell unchanged, so they are skipped without being pasted, though they still take
+    /// up their cell.
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas.
+    /// * `color_space` - The color space to blend in.
+    /// # Panics
+    /// This function will panic if there is not enough space on the canvas to fit all the images, or if any image does not
+    /// match the image dimensions of the merger.
+    pub fn bulk_push_blended<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+        color_space: ColorSpace,
+    ) where
+        P: AlphaPixel,
+        SourceContainer: DerefMut<Target = [u8]> + Sync,
+    {
+        let cells = self.next_empty_cells(images.len());
+        self.check_dimensions(images)
+            .expect("Every image must match the image dimensions of the merger!");
+        let locations: Vec<Point> = cells
+            .iter()
+            .map(|&index| {
+                let (x, y) = self.get_paste_coordinates_unchecked(index);
+                Point { x, y }
+            })
+            .collect();
+
+        let blend_one = |index: usize| {
+            if !is_fully_transparent(images[index]) {
+                paste_blended(&self.canvas, images[index], locations[index], color_space);
+            }
+        };
+        if self.placement.is_some() && self.any_overlap(&locations) {
+            // Blending depends on what is already in the cell, so overlapping images must be blended in order.
+            (0..images.len()).for_each(blend_one);
+        } else {
+            self.install(|| (0..images.len()).into_par_iter().for_each(blend_one));
+        }
+
+        self.fill_cells(&cells);
+    }
+
+    /// Pushes an image whose channels are in BGRA order onto the canvas, such as a frame captured from a GPU. The red and blue
+    /// channels are swapped while the image is pasted, so no reordered copy is made. On a canvas of 3 channel pixels, such as
+    /// `Rgb<u8>`, the image is read in BGR order instead.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas, in BGRA or BGR order.
+    /// # Panics
+    /// This function will panic if the pixel type has fewer than 3 channels, if there is no more space on the canvas, or if the
+    /// image does not match the image dimensions of the merger.
+    pub fn push_bgra<SourceContainer>(
+        &mut self,
+        image: &Image<P, image::ImageBuffer<P, SourceContainer>>,
+    ) where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        assert_eq!(
+            image.dimensions(),
+            self.image_dimensions,
+            "The image must match the image dimensions of the merger!"
+        );
+        let (x, y) = self.get_next_paste_coordinates();
+        self.install(|| paste_bgr(&self.canvas, image, Point { x, y }));
+
+        self.last_pasted_index += 1;
+        let index = self.last_pasted_index as u32;
+        self.mark_occupied(index..index + 1);
+    }
+
+    /// Pushes a rectangular region of an image onto the canvas, such as to discard the borders of a tile. The region is read
+    /// straight out of the image, so no cropped copy of it is allocated.
+    /// # Arguments
+    /// * `image` - The image to push a region of.
+    /// * `src_rect` - The region to push, as `(x, y, width, height)`. Its width and height must match the image dimensions of
+    ///   the merger.
+    /// # Returns
+    /// * `Ok` - If the region was pasted.
+    /// * `Err(MergerError::DimensionMismatch)` - If the size of the region differs from the image dimensions of the merger.
+    /// # Panics
+    /// This function will panic if the region extends past the edge of the image, or if there is no more space on the canvas.
+    pub fn push_cropped<SourceContainer>(
+        &mut self,
+        image: &Image<P, image::ImageBuffer<P, SourceContainer>>,
+        src_rect: (u32, u32, u32, u32),
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let (src_x, src_y, width, height) = src_rect;
+        if (width, height) != self.image_dimensions {
+            return Err(MergerError::DimensionMismatch {
+                index: None,
+                expected: self.image_dimensions,
+                got: (width, height),
+            });
+        }
+
+        let (x, y) = self.get_next_paste_coordinates();
+        let src = Point { x: src_x, y: src_y };
+        self.install(|| paste_region(&self.canvas, image, src, (width, height), Point { x, y }));
+
+        self.last_pasted_index += 1;
+        let index = self.last_pasted_index as u32;
+        self.mark_occupied(index..index + 1);
+
+        Ok(())
+    }
+
+    /// Pushes an image that is smaller than the image dimensions of the merger onto the canvas, centered in its cell. The rest
+    /// of the cell is filled with the given color. When the image does not split evenly, the extra pixel goes to the right
+    /// and bottom. Images larger than the cell are rejected rather than cropped.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas.
+    /// * `fill` - The color to fill the rest of the cell with.
+    /// # Returns
+    /// * `Ok` - If the image was pasted.
+    /// * `Err(MergerError::DimensionMismatch)` - If the image is wider or taller than the image dimensions of the merger.
+    /// # Panics
+    /// This function will panic if there is no more space on the canvas.
+    pub fn push_centered(&mut self, image: &BufferedImage<P>, fill: P) -> Result<(), MergerError> {
+        self.push_anchored(image, Anchor::Center, fill)
+    }
+
+    /// Pushes an image that is smaller than the image dimensions of the merger onto the canvas, placed within its cell by
+    /// the given anchor, such as pinned to a corner. The rest of the cell is filled with the given color. Images larger than
+    /// the cell are rejected rather than cropped.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas.
+    /// * `anchor` - Where the image is placed within its cell.
+    /// * `fill` - The color to fill the rest of the cell with.
+    /// # Returns
+    /// * `Ok` - If the image was pasted.
+    /// * `Err(MergerError::DimensionMismatch)` - If the image is wider or taller than the image dimensions of the merger.
+    /// # Panics
+    /// This function will panic if there is no more space on the canvas.
+    pub fn push_anchored(
+        &mut self,
+        image: &BufferedImage<P>,
+        anchor: Anchor,
+        fill: P,
+    ) -> Result<(), MergerError> {
+        let (width, height) = self.image_dimensions;
+        if image.width() > width || image.height() > height {
+            return Err(MergerError::DimensionMismatch {
+                index: None,
+                expected: self.image_dimensions,
+                got: image.dimensions(),
+            });
+        }
+
+        let cell = ImageCell::new(BufferedImage::new_from_pixel(width, height, fill));
+        let offset = anchor.offset(image.dimensions(), self.image_dimensions);
+        paste_sequential(&cell, image, offset);
+        self.push(&cell.into_inner());
+
+        Ok(())
+    }
+
+    /// Reads and decodes the images at the given paths in parallel, then bulk pushes them onto the canvas in the order they
+    /// were given. The format of each image is guessed from its contents.
+    /// # Arguments
+    /// * `paths` - The paths of the images to push onto the canvas.
+    /// # Returns
+    /// * `Ok` - If all the images were pushed onto the canvas.
+    /// * `Err(MergerError::Decode)` - If an image could not be read or decoded. Nothing is pushed onto the canvas in this case.
+    /// * `Err(MergerError::DimensionMismatch)` - If an image does not match the image dimensions of the merger. The `index` is
+    ///   the position of its path in `paths`. Nothing is pushed onto the canvas in this case.
+    /// # Panics
+    /// This function will panic if there is not enough space on the canvas to fit all the images.
+    pub fn bulk_push_paths<Pth>(&mut self, paths: &[Pth]) -> Result<(), MergerError>
+    where
+        Pth: AsRef<Path> + Sync,
+        P: Send,
+        <P as Pixel>::Subpixel: Send,
+        BufferedImage<P>: TryFromWithFormat<Vec<u8>>,
+    {
+        let images = self.install(|| {
+            paths
+                .par_iter()
+                .map(|path| {
+                    let path = path.as_ref();
+                    let decode_error = |source| MergerError::Decode {
+                        path: path.to_path_buf(),
+                        source,
+                    };
+
+                    let buffer = std::fs::read(path)
+                        .map_err(|error| decode_error(image::ImageError::IoError(error)))?;
+                    let format = image::guess_format(&buffer).map_err(decode_error)?;
+
+                    BufferedImage::try_from_with_format(buffer, format).map_err(decode_error)
+                })
+                .collect::<Result<Vec<BufferedImage<P>>, MergerError>>()
+        })?;
+
+        // Convert Vec<T> to [&T] for the bulk push method
+        let images_ref: Vec<&BufferedImage<P>> = images.iter().collect();
+        self.try_bulk_push(&images_ref)
+    }
+
+    /// Same as `bulk_push_paths`, but runs the whole decode and merge on tokio's blocking thread pool, then hands back the
+    /// finished canvas. This keeps a large merge from stalling the async runtime it was started from.
+    /// # Arguments
+    /// * `paths` - The paths of the images to push onto the canvas.
+    /// # Returns
+    /// A handle that resolves to the finished canvas, or to the `MergerError::Decode` of the first image that could not be
+    /// read or decoded, or the `MergerError::DimensionMismatch` of the first image with the wrong dimensions.
+    /// # Panics
+    /// This function will panic if it is not called from within a tokio runtime. The merge itself panics, which surfaces as
+    /// an error from the handle, if there is not enough space on the canvas to fit all the images.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use image_merger::{KnownSizeMerger, Rgba};
+    ///
+    /// # async fn merge() {
+    /// let merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((100, 100), 2, 2, None);
+    /// let canvas = merger
+    ///     .merge_paths_blocking(vec!["a.png", "b.png"])
+    ///     .await
+    ///     .unwrap()
+    ///     .unwrap();
+    /// # }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub fn merge_paths_blocking<Pth>(
+        mut self,
+        paths: Vec<Pth>,
+    ) -> tokio::task::JoinHandle<Result<BufferedImage<P>, MergerError>>
+    where
+        Pth: AsRef<Path> + Send + Sync + 'static,
+        P: Send + 'static,
+        <P as Pixel>::Subpixel: Send,
+        BufferedImage<P>: TryFromWithFormat<Vec<u8>>,
+    {
+        tokio::task::spawn_blocking(move || {
+            self.bulk_push_paths(&paths)?;
+            Ok(self.into_canvas())
+        })
+    }
+
+    /// Decodes the given in-memory images in parallel, then bulk pushes them onto the canvas in the order they were given.
+    /// # Arguments
+    /// * `buffers` - The encoded images to push onto the canvas.
+    /// * `format` - The format all of the images are encoded with.
+    /// # Returns
+    /// * `Ok` - If all the images were pushed onto the canvas.
+    /// * `Err(MergerError::DecodeBuffer)` - If an image could not be decoded. Nothing is pushed onto the canvas in this case.
+    /// * `Err(MergerError::DimensionMismatch)` - If an image does not match the image dimensions of the merger. The `index` is
+    ///   the position of its buffer in `buffers`. Nothing is pushed onto the canvas in this case.
+    /// # Panics
+    /// This function will panic if there is not enough space on the canvas to fit all the images.
+    pub fn bulk_push_encoded(
+        &mut self,
+        buffers: &[&[u8]],
+        format: ImageFormat,
+    ) -> Result<(), MergerError>
+    where
+        P: Send,
+        <P as Pixel>::Subpixel: Send,
+        for<'a> BufferedImage<P>: TryFromWithFormat<&'a [u8]>,
+    {
+        let images = self.install(|| {
+            buffers
+                .par_iter()
+                .enumerate()
+                .map(|(index, buffer)| {
+                    BufferedImage::try_from_with_format(*buffer, format)
+                        .map_err(|source| MergerError::DecodeBuffer { index, source })
+                })
+                .collect::<Result<Vec<BufferedImage<P>>, MergerError>>()
+        })?;
+
+        // Convert Vec<T> to [&T] for the bulk push method
+        let images_ref: Vec<&BufferedImage<P>> = images.iter().collect();
+        self.try_bulk_push(&images_ref)
+    }
+}
+
+impl<P, Container> Merger<P, Container> for KnownSizeMerger<P, Container>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+    Container: DerefMut<Target = [P::Subpixel]> + Sync,
+{
+    fn get_canvas(&self) -> &Image<P, image::ImageBuffer<P, Container>> {
+        &self.canvas
+    }
+
+    fn get_canvas_mut(&mut self) -> &mut Image<P, image::ImageBuffer<P, Container>> {
+        // Holding `&mut self` guarantees no handouts to the canvas are alive.
+        self.canvas.get_image_mut()
+    }
+
+    fn into_canvas(self) -> Image<P, image::ImageBuffer<P, Container>> {
+        self.canvas.into_inner()
+    }
+
+    fn get_num_images(&self) -> u32 {
+        self.num_images
+    }
+
+    fn trim_to_content(&self) -> BufferedImage<P>
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        let (loc, dimensions) = bounding_box(
+            self.cell_rects()
+                .map(|(_, loc, dimensions)| (loc, dimensions)),
+        );
+        crop(&self.canvas, loc, dimensions)
+    }
+
+    fn push<SourceContainer>(&mut self, image: &Image<P, image::ImageBuffer<P, SourceContainer>>)
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        // A larger image would spill over into the neighbouring cells, the same as with `bulk_push`.
+        assert!(
+            !self.needs_resize(image.dimensions()),
+            "The image must match the image dimensions of the merger!"
+        );
+        let (x, y) = self.get_next_paste_coordinates();
+        self.paste_image(image, Point { x, y });
+
+        self.last_pasted_index += 1;
+        let index = self.last_pasted_index as u32;
+        self.mark_occupied(index..index + 1);
+    }
+
+    fn bulk_push<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, image::ImageBuffer<P, SourceContainer>>],
+    ) where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        self.bulk_push_with_progress(images, |_| {});
+    }
+}
+
+/// An iterator over the images on a merger's canvas, created by `into_iter` on [KnownSizeMerger](KnownSizeMerger). Each
+/// image is copied out of the canvas as it is reached, in the order of their cells.
+pub struct IntoImages<P, Container>
+where
+    P: Pixel,
+    <P as Pixel>::Subpixel: Sync,
+    Container: DerefMut<Target = [P::Subpixel]> + Sync,
+{
+    merger: KnownSizeMerger<P, Container>,
+    next_index: u32, // The index of the next cell to check for an image.
+}
+
+impl<P, Container> Iterator for IntoImages<P, Container>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync + Send,
+    Container: DerefMut<Target = [P::Subpixel]> + Sync,
+{
+    type Item = BufferedImage<P>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let merger = &self.merger;
+        let index = (self.next_index..merger.occupied.len() as u32)
+            .find(|&index| merger.occupied[index as usize])?;
+        self.next_index = index + 1;
+
+        let (x, y) = merger.get_paste_coordinates_unchecked(index);
+        Some(crop(
+            &merger.canvas,
+            Point { x, y },
+            merger.image_dimensions,
+        ))
+    }
+}
+
+impl<P, Container> IntoIterator for KnownSizeMerger<P, Container>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync + Send,
+    Container: DerefMut<Target = [P::Subpixel]> + Sync,
+{
+    type Item = BufferedImage<P>;
+    type IntoIter = IntoImages<P, Container>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoImages {
+            merger: self,
+            next_index: 0,
+        }
+    }
+}
+
+/// Two mergers are equal if they have the same layout, hold the same number of images, and their canvases are identical.
+impl<P, Container> PartialEq for KnownSizeMerger<P, Container>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+    Container: DerefMut<Target = [P::Subpixel]> + Sync,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let canvas: &[<P as Pixel>::Subpixel] = &self.canvas;
+        let other_canvas: &[<P as Pixel>::Subpixel] = &other.canvas;
+
+        self.image_dimensions == other.image_dimensions
+            && self.images_per_row == other.images_per_row
+            && self.total_rows == other.total_rows
+            && self.capacity() == other.capacity()
+            && self.num_images == other.num_images
+            && self.padding == other.padding
+            && self.edge_padding == other.edge_padding
+            && self.margin == other.margin
+            && self.canvas.dimensions() == other.canvas.dimensions()
+            && canvas == other_canvas
+    }
+}
+
+/// Prints the layout of the merger, leaving out the pixels of the canvas.
+impl<P, Container> std::fmt::Debug for KnownSizeMerger<P, Container>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+    Container: DerefMut<Target = [P::Subpixel]> + Sync,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KnownSizeMerger")
+            .field("canvas_dimensions", &self.canvas.dimensions())
+            .field("image_dimensions", &self.image_dimensions)
+            .field("num_images", &self.num_images)
+            .field("images_per_row", &self.images_per_row)
+            .field("total_rows", &self.total_rows)
+            .field("padding", &self.padding)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P> ResizableMerger<P> for KnownSizeMerger<P, Vec<<P as Pixel>::Subpixel>>
+where
+    P: Pixel + Sync + Send,
+    <P as Pixel>::Subpixel: Sync + Send,
+{
+    fn push_resized(&mut self, image: &BufferedImage<P>) {
+        if !self.needs_resize(image.dimensions()) {
+            return self.push(image);
+        }
+
+        let (width, height) = self.image_dimensions;
+        let resized = self.install(|| resize_nearest_neighbor(image, width, height));
+        self.push(&resized);
+    }
+
+    fn bulk_push_resized(&mut self, images: &[&BufferedImage<P>]) {
+        // Resize all the images in parallel then push them
+        let resized_images: Vec<BufferedImage<P>> = self.install(|| {
+            images
+                .into_par_iter()
+                .map(|image| {
+                    let (width, height) = self.image_dimensions;
+                    resize_nearest_neighbor(image, width, height)
+                })
+                .collect()
+        });
+
+        // Convert Vec<T> to [&T] for the bulk push method
+        let resized_images_ref: Vec<&BufferedImage<P>> = resized_images.iter().collect();
+
+        self.bulk_push(&resized_images_ref);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn test_cached_offsets_match_formula() {
+        let edges = EdgePadding {
+            top: 1,
+            right: 2,
+            bottom: 3,
+            left: 4,
+        };
+        let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::builder()
+            .image_dimensions((7, 5))
+            .images_per_row(10)
+            .total_images(100)
+            .padding(Padding { x: 3, y: 2 })
+            .edge_padding(edges)
+            .margin(Point { x: 6, y: 8 })
+            .build();
+        merger.grow(10);
+
+        for index in 0..merger.capacity() {
+            let (column, row) = (index % 10, index / 10);
+            let x = column * (7 + 4 + 2) + column * 3 + 4 + 6;
+            let y = row * (5 + 1 + 3) + row * 2 + 1 + 8;
+            assert_eq!(merger.get_paste_coordinates_unchecked(index), (x, y));
+        }
     }
 }
@@ -1,7 +1,13 @@
+mod aggregate;
 mod core;
+mod fixed;
 mod known;
+mod non_uniform;
 mod resizable;
 
+pub use aggregate::*;
 pub use core::*;
+pub use fixed::*;
 pub use known::*;
+pub use non_uniform::*;
 pub use resizable::*;
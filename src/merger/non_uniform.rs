@@ -0,0 +1,231 @@
+use super::core::{bounding_box, Merger, Point};
+use crate::{
+    cell::ImageCell,
+    functions::{crop, paste},
+    parallel::*,
+    BufferedImage, Image, MergerError,
+};
+
+use image::{ImageBuffer, Pixel};
+use std::ops::DerefMut;
+
+/// A merger whose columns and rows can each have a different size. This is useful for contact sheets where, for example,
+/// each row has a caption of a different height. Images are pasted at the top left of their cell, and must fit within it.
+///
+/// # Type Parameters
+/// * `P` - The pixel type of the underlying image.
+///
+/// # Example
+/// ```
+/// use image_merger::{Merger, NonUniformMerger, Image, Rgb};
+///
+/// let mut merger: NonUniformMerger<Rgb<u8>> = NonUniformMerger::new(vec![100, 50], vec![100, 80]);
+/// merger.push(&Image::new(100, 100));
+/// merger.push(&Image::new(50, 50));
+/// assert_eq!(merger.get_canvas().dimensions(), (150, 180));
+/// ```
+pub struct NonUniformMerger<P>
+where
+    P: Pixel,
+    <P as Pixel>::Subpixel: Sync,
+{
+    canvas: ImageCell<P, image::ImageBuffer<P, Vec<P::Subpixel>>>,
+    col_widths: Vec<u32>,
+    row_heights: Vec<u32>,
+    col_offsets: Vec<u32>, // The x coordinate each column starts at.
+    row_offsets: Vec<u32>, // The y coordinate each row starts at.
+    num_images: u32,       // The number of images that have been pasted to the canvas
+}
+
+/// Returns the offset each size starts at when laid out one after another, along with the total size.
+fn prefix_sums(sizes: &[u32]) -> (Vec<u32>, u32) {
+    let mut total = 0;
+    let offsets = sizes
+        .iter()
+        .map(|size| {
+            let offset = total;
+            total += size;
+            offset
+        })
+        .collect();
+
+    (offsets, total)
+}
+
+impl<P> NonUniformMerger<P>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+{
+    /// Constructs a new NonUniformMerger.
+    /// # Arguments
+    /// * `col_widths` - The width of each column, from left to right.
+    /// * `row_heights` - The height of each row, from top to bottom.
+    pub fn new(col_widths: Vec<u32>, row_heights: Vec<u32>) -> Self {
+        let (col_offsets, width) = prefix_sums(&col_widths);
+        let (row_offsets, height) = prefix_sums(&row_heights);
+
+        Self {
+            canvas: ImageCell::new(Image::new(width, height)),
+            col_widths,
+            row_heights,
+            col_offsets,
+            row_offsets,
+            num_images: 0,
+        }
+    }
+
+    /// Returns the top left point and the dimensions of the cell at the given index. Indices start at 0 and work left to
+    /// right, top to bottom.
+    pub fn get_cell(&self, index: u32) -> Option<(Point, (u32, u32))> {
+        let columns = self.col_widths.len() as u32;
+        if columns == 0 {
+            return None;
+        }
+
+        let column = (index % columns) as usize;
+        let row = (index / columns) as usize;
+
+        let y = *self.row_offsets.get(row)?;
+        let point = Point {
+            x: self.col_offsets[column],
+            y,
+        };
+
+        Some((point, (self.col_widths[column], self.row_heights[row])))
+    }
+
+    /// Returns the location the image at the given index will be pasted at, or an error if it does not fit in its cell.
+    fn paste_location<SourceContainer>(
+        &self,
+        index: u32,
+        image: &Image<P, ImageBuffer<P, SourceContainer>>,
+    ) -> Result<Point, MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]>,
+    {
+        let capacity = (self.col_widths.len() * self.row_heights.len()) as u32;
+        let (point, (width, height)) = self
+            .get_cell(index)
+            .ok_or(MergerError::IndexOutOfBounds { index, capacity })?;
+
+        if image.width() > width || image.height() > height {
+            return Err(MergerError::DimensionMismatch {
+                index: None,
+                expected: (width, height),
+                got: image.dimensions(),
+            });
+        }
+
+        Ok(point)
+    }
+
+    /// Pushes an image into the next cell on the canvas.
+    /// # Arguments
+    /// * `image` - The image to push onto the canvas.
+    /// # Returns
+    /// * `Ok` - If the image was pasted.
+    /// * `Err(MergerError::IndexOutOfBounds)` - If there is no more space on the canvas.
+    /// * `Err(MergerError::DimensionMismatch)` - If the image is larger than its cell.
+    pub fn try_push<SourceContainer>(
+        &mut self,
+        image: &Image<P, ImageBuffer<P, SourceContainer>>,
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]>,
+    {
+        let point = self.paste_location(self.num_images, image)?;
+        paste(&self.canvas, image, point);
+        self.num_images += 1;
+
+        Ok(())
+    }
+
+    /// Bulk pushes images into the next cells on the canvas. Every image is validated before any are pasted.
+    /// # Arguments
+    /// * `images` - The images to push onto the canvas.
+    /// # Returns
+    /// * `Ok` - If the images were pasted.
+    /// * `Err(MergerError::IndexOutOfBounds)` - If there is not enough space on the canvas for all the images.
+    /// * `Err(MergerError::DimensionMismatch)` - If an image is larger than its cell.
+    pub fn try_bulk_push<SourceContainer>(
+        &mut self,
+        images: &[&Image<P, ImageBuffer<P, SourceContainer>>],
+    ) -> Result<(), MergerError>
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        let points = images
+            .iter()
+            .enumerate()
+            .map(|(offset, image)| {
+                self.paste_location(self.num_images + offset as u32, image)
+                    .map_err(|error| match error {
+                        MergerError::DimensionMismatch { expected, got, .. } => {
+                            MergerError::DimensionMismatch {
+                                index: Some(offset as u32),
+                                expected,
+                                got,
+                            }
+                        }
+                        error => error,
+                    })
+            })
+            .collect::<Result<Vec<Point>, MergerError>>()?;
+
+        (0..images.len()).into_par_iter().for_each(|index| {
+            paste(&self.canvas, images[index], points[index]);
+        });
+        self.num_images += images.len() as u32;
+
+        Ok(())
+    }
+}
+
+impl<P> Merger<P, Vec<P::Subpixel>> for NonUniformMerger<P>
+where
+    P: Pixel + Sync,
+    <P as Pixel>::Subpixel: Sync,
+{
+    fn get_canvas(&self) -> &BufferedImage<P> {
+        &self.canvas
+    }
+
+    fn get_num_images(&self) -> u32 {
+        self.num_images
+    }
+
+    fn trim_to_content(&self) -> BufferedImage<P>
+    where
+        <P as Pixel>::Subpixel: Send,
+    {
+        let (loc, dimensions) =
+            bounding_box((0..self.num_images).filter_map(|index| self.get_cell(index)));
+        crop(&self.canvas, loc, dimensions)
+    }
+
+    fn get_canvas_mut(&mut self) -> &mut BufferedImage<P> {
+        // Holding `&mut self` guarantees no handouts to the canvas are alive.
+        self.canvas.get_image_mut()
+    }
+
+    fn into_canvas(self) -> BufferedImage<P> {
+        self.canvas.into_inner()
+    }
+
+    fn push<SourceContainer>(&mut self, image: &Image<P, ImageBuffer<P, SourceContainer>>)
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        self.try_push(image)
+            .expect("The image could not be pushed onto the canvas!");
+    }
+
+    fn bulk_push<SourceContainer>(&mut self, images: &[&Image<P, ImageBuffer<P, SourceContainer>>])
+    where
+        SourceContainer: DerefMut<Target = [P::Subpixel]> + Sync,
+    {
+        self.try_bulk_push(images)
+            .expect("The images could not be pushed onto the canvas!");
+    }
+}
@@ -0,0 +1,178 @@
+//! The parallel iterators used throughout the crate. With the default `rayon` feature, these are rayon's own, and work is
+//! spread across the rayon thread pool. Without it, the [serial](serial) module provides the same method names over plain
+//! iterators, so every function compiles unchanged and produces identical output on the current thread. This is useful for
+//! targets rayon does not support, such as WASM.
+#[cfg(feature = "rayon")]
+pub(crate) use rayon::{prelude::*, ThreadPool};
+
+#[cfg(not(feature = "rayon"))]
+pub(crate) use serial::*;
+
+/// Serial stand-ins for the rayon traits used by this crate. Each method simply returns the equivalent standard iterator.
+/// These are always compiled so they can be tested against rayon.
+#[cfg_attr(feature = "rayon", allow(dead_code))]
+pub(crate) mod serial {
+    /// Stand-in for rayon's `IntoParallelIterator`.
+    pub(crate) trait IntoParallelIterator: IntoIterator + Sized {
+        fn into_par_iter(self) -> Self::IntoIter {
+            self.into_iter()
+        }
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I {}
+
+    /// Stand-in for rayon's `IntoParallelRefIterator`.
+    pub(crate) trait IntoParallelRefIterator<'data> {
+        type Iter: Iterator;
+
+        fn par_iter(&'data self) -> Self::Iter;
+    }
+
+    impl<'data, I: 'data + ?Sized> IntoParallelRefIterator<'data> for I
+    where
+        &'data I: IntoIterator,
+    {
+        type Iter = <&'data I as IntoIterator>::IntoIter;
+
+        fn par_iter(&'data self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+
+    /// Stand-in for rayon's `IntoParallelRefMutIterator`.
+    pub(crate) trait IntoParallelRefMutIterator<'data> {
+        type Iter: Iterator;
+
+        fn par_iter_mut(&'data mut self) -> Self::Iter;
+    }
+
+    impl<'data, I: 'data + ?Sized> IntoParallelRefMutIterator<'data> for I
+    where
+        &'data mut I: IntoIterator,
+    {
+        type Iter = <&'data mut I as IntoIterator>::IntoIter;
+
+        fn par_iter_mut(&'data mut self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+
+    /// Stand-in for rayon's `ParallelIterator`. Only the methods that are not already on `Iterator` are needed.
+    pub(crate) trait ParallelIterator: Iterator + Sized {
+        /// Rayon uses this to limit how finely work is split. There is nothing to split on a single thread.
+        fn with_min_len(self, _min: usize) -> Self {
+            self
+        }
+
+        /// Rayon searches in parallel but still returns the first match, which is all a single thread can find anyway.
+        fn position_first<F>(mut self, predicate: F) -> Option<usize>
+        where
+            F: FnMut(Self::Item) -> bool,
+        {
+            self.position(predicate)
+        }
+    }
+
+    impl<I: Iterator> ParallelIterator for I {}
+
+    /// Stand-in for rayon's `ParallelSlice`.
+    pub(crate) trait ParallelSlice<T> {
+        fn par_chunks(&self, chunk_size: usize) -> std::slice::Chunks<'_, T>;
+
+        fn par_chunks_exact(&self, chunk_size: usize) -> std::slice::ChunksExact<'_, T>;
+    }
+
+    impl<T> ParallelSlice<T> for [T] {
+        fn par_chunks(&self, chunk_size: usize) -> std::slice::Chunks<'_, T> {
+            self.chunks(chunk_size)
+        }
+
+        fn par_chunks_exact(&self, chunk_size: usize) -> std::slice::ChunksExact<'_, T> {
+            self.chunks_exact(chunk_size)
+        }
+    }
+
+    /// Stand-in for rayon's `ParallelSliceMut`.
+    pub(crate) trait ParallelSliceMut<T> {
+        fn par_chunks_mut(&mut self, chunk_size: usize) -> std::slice::ChunksMut<'_, T>;
+
+        fn par_chunks_exact_mut(&mut self, chunk_size: usize) -> std::slice::ChunksExactMut<'_, T>;
+    }
+
+    impl<T> ParallelSliceMut<T> for [T] {
+        fn par_chunks_mut(&mut self, chunk_size: usize) -> std::slice::ChunksMut<'_, T> {
+            self.chunks_mut(chunk_size)
+        }
+
+        fn par_chunks_exact_mut(&mut self, chunk_size: usize) -> std::slice::ChunksExactMut<'_, T> {
+            self.chunks_exact_mut(chunk_size)
+        }
+    }
+
+    /// Stand-in for rayon's `ThreadPool`. It can not be constructed, so a merger without the `rayon` feature always runs its
+    /// work on the current thread.
+    #[derive(Debug)]
+    pub(crate) enum ThreadPool {}
+
+    impl ThreadPool {
+        pub(crate) fn install<OP, R>(&self, _op: OP) -> R
+        where
+            OP: FnOnce() -> R,
+        {
+            match *self {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::serial::{
+        IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator,
+        ParallelIterator, ParallelSlice, ParallelSliceMut,
+    };
+
+    #[test]
+    fn test_serial_iterators() {
+        let values: Vec<u32> = (0..10).collect();
+        let doubled: Vec<u32> = values.par_iter().map(|v| v * 2).collect();
+        assert_eq!(doubled, (0..20).step_by(2).collect::<Vec<u32>>());
+
+        let summed: Vec<u32> = (0..4u32).into_par_iter().with_min_len(256).collect();
+        assert_eq!(summed, vec![0, 1, 2, 3]);
+
+        let mut values = values;
+        values.par_iter_mut().for_each(|v| *v += 1);
+        assert_eq!(values, (1..11).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn test_serial_chunks() {
+        let mut values: Vec<u8> = (0..10).collect();
+        let sums: Vec<u8> = values.par_chunks_exact(3).map(|c| c.iter().sum()).collect();
+        assert_eq!(sums, vec![3, 12, 21]);
+        assert_eq!(values.par_chunks(3).count(), 4);
+
+        values.par_chunks_exact_mut(2).for_each(|c| c.swap(0, 1));
+        assert_eq!(&values[..4], &[1, 0, 3, 2]);
+        values.par_chunks_mut(4).for_each(|c| c[0] = 0);
+        assert_eq!(values[8], 0);
+    }
+
+    /// The serial helpers must match rayon's output exactly, as they are swapped in for it when the feature is off.
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_serial_matches_rayon() {
+        use rayon::prelude::{IndexedParallelIterator as _, ParallelIterator as _};
+
+        let values: Vec<u16> = (0..1000).collect();
+        let serial: Vec<u16> = ParallelSlice::par_chunks_exact(&values[..], 7)
+            .enumerate()
+            .map(|(i, c)| c.iter().sum::<u16>() ^ i as u16)
+            .collect();
+        let parallel: Vec<u16> = rayon::slice::ParallelSlice::par_chunks_exact(&values[..], 7)
+            .enumerate()
+            .map(|(i, c)| c.iter().sum::<u16>() ^ i as u16)
+            .collect();
+        assert_eq!(serial, parallel);
+    }
+}
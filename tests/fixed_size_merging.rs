@@ -0,0 +1,139 @@
+use image_merger::*;
+
+type RgbImageBuffer = BufferedImage<Rgb<u8>>;
+
+#[test]
+fn test_push_resizes_images() {
+    let mut merger: FixedSizeMerger<Rgb<u8>> = FixedSizeMerger::new((10, 10), 2, 2, None);
+    merger.push(&RgbImageBuffer::new_from_pixel(40, 20, Rgb([255, 0, 0])));
+    merger.push(&RgbImageBuffer::new_from_pixel(10, 10, Rgb([0, 255, 0])));
+
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.dimensions(), (20, 20));
+    assert_eq!(*canvas.get_pixel(9, 9), Rgb([255, 0, 0]));
+    assert_eq!(*canvas.get_pixel(10, 0), Rgb([0, 255, 0]));
+    assert_eq!(*canvas.get_pixel(0, 10), Rgb([0, 0, 0]));
+    assert_eq!(merger.get_num_images(), 2);
+}
+
+#[test]
+fn test_bulk_push_matches_known_size_merger() {
+    let images: Vec<RgbImageBuffer> = (0..4u8)
+        .map(|i| RgbImageBuffer::new_from_pixel(30 + i as u32, 25, Rgb([i * 60, 0, 0])))
+        .collect();
+    let images: Vec<&RgbImageBuffer> = images.iter().collect();
+    let padding = Some(Padding { x: 3, y: 2 });
+
+    let mut merger: FixedSizeMerger<Rgb<u8>> = FixedSizeMerger::new((10, 10), 2, 2, padding);
+    merger.bulk_push(&images);
+
+    let mut expected: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((10, 10), 2, 4, padding);
+    expected.bulk_push_resized(&images);
+
+    assert!(merger
+        .into_canvas()
+        .pixels()
+        .eq(expected.get_canvas().pixels()));
+}
+
+#[test]
+fn test_needs_resize() {
+    let merger: FixedSizeMerger<Rgb<u8>> = FixedSizeMerger::new((10, 20), 2, 2, None);
+    assert!(!merger.needs_resize((10, 20)));
+    assert!(merger.needs_resize((20, 10)));
+    assert!(merger.needs_resize((10, 21)));
+
+    let merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((10, 20), 2, 4, None);
+    assert!(!merger.needs_resize((10, 20)));
+    assert!(merger.needs_resize((9, 20)));
+}
+
+#[test]
+fn test_with_filter() {
+    // A smooth gradient with a fine checker pattern on top, which each filter samples differently when shrunk.
+    let photo = RgbImageBuffer::from(image::ImageBuffer::from_fn(200, 200, |x, y| {
+        let checker = if (x + y) % 2 == 0 { 0 } else { 60 };
+        Rgb([(x / 2 + checker) as u8, (y / 2 + checker) as u8, 128])
+    }));
+
+    let merge = |filter| {
+        let mut merger: FixedSizeMerger<Rgb<u8>> =
+            FixedSizeMerger::new((20, 20), 2, 1, None).with_filter(filter);
+        merger.push(&photo);
+        merger.bulk_push(&[&photo]);
+        merger.into_canvas()
+    };
+    let nearest = merge(ResampleFilter::Nearest);
+    let bilinear = merge(ResampleFilter::Bilinear);
+
+    assert_eq!(bilinear.dimensions(), (40, 20));
+    assert_ne!(nearest.get_pixel(10, 10), bilinear.get_pixel(10, 10));
+    assert_ne!(nearest.get_pixel(30, 10), bilinear.get_pixel(30, 10));
+    assert_eq!(bilinear.get_pixel(10, 10), bilinear.get_pixel(30, 10));
+}
+
+#[test]
+fn test_with_contain_bilinear() {
+    let fill = Rgb([255, 255, 255]);
+    let red = Rgb([200, 0, 0]);
+    let landscape = RgbImageBuffer::new_from_pixel(300, 100, red);
+    let portrait = RgbImageBuffer::new_from_pixel(100, 300, red);
+
+    let mut merger: FixedSizeMerger<Rgb<u8>> = FixedSizeMerger::with(
+        (100, 100),
+        2,
+        2,
+        None,
+        FitMode::Contain,
+        ResampleFilter::Bilinear,
+        fill,
+    );
+    merger.push(&landscape);
+    merger.bulk_push(&[&portrait, &landscape]);
+    assert_eq!(merger.get_fit(), FitMode::Contain);
+
+    // The landscape photo is shrunk to 100x33 and centered, leaving bars above and below it.
+    for (x, y) in [(50, 0), (50, 32), (0, 66), (99, 99)] {
+        assert_eq!(merger.canvas_pixel(x, y), Some(fill), "({x}, {y})");
+        assert_eq!(merger.canvas_pixel(x, y + 100), Some(fill), "({x}, {y})");
+    }
+    for (x, y) in [(0, 33), (50, 50), (99, 65)] {
+        assert_eq!(merger.canvas_pixel(x, y), Some(red), "({x}, {y})");
+        assert_eq!(merger.canvas_pixel(x, y + 100), Some(red), "({x}, {y})");
+    }
+
+    // The portrait photo is shrunk to 33x100 and centered, leaving bars to its left and right.
+    for (x, y) in [(100, 50), (132, 0), (166, 99), (199, 50)] {
+        assert_eq!(merger.canvas_pixel(x, y), Some(fill), "({x}, {y})");
+    }
+    for (x, y) in [(133, 0), (150, 50), (165, 99)] {
+        assert_eq!(merger.canvas_pixel(x, y), Some(red), "({x}, {y})");
+    }
+}
+
+#[test]
+fn test_with_cover() {
+    // Thirds of red, green, and blue, of which only the middle one is kept when covering a square cell.
+    let photo = RgbImageBuffer::from(image::ImageBuffer::from_fn(300, 100, |x, _| {
+        match x / 100 {
+            0 => Rgb([255, 0, 0]),
+            1 => Rgb([0, 255, 0]),
+            _ => Rgb([0, 0, 255]),
+        }
+    }));
+
+    let mut merger: FixedSizeMerger<Rgb<u8>> = FixedSizeMerger::with(
+        (100, 100),
+        1,
+        1,
+        None,
+        FitMode::Cover,
+        ResampleFilter::Nearest,
+        Rgb([0, 0, 0]),
+    );
+    merger.push(&photo);
+
+    let canvas = merger.into_canvas();
+    assert_eq!(canvas.dimensions(), (100, 100));
+    assert!(canvas.pixels().all(|pixel| *pixel == Rgb([0, 255, 0])));
+}
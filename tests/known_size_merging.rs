@@ -11,26 +11,6 @@ static IMAGE_HEIGHT: u32 = 100;
 
 type RgbaImageBuffer = BufferedImage<Rgba<u8>>;
 
-// TODO: Move this to a test utils file, make it generic, and take in an image index for unique colors
-// across an entire image.
-fn generate_test_square() -> RgbaImageBuffer {
-    let color = |x: u32, y: u32| -> Rgba<u8> {
-        let r = x as u8;
-        let g = y as u8;
-        let b = (x + y) as u8;
-        Rgba([r, g, b, 255])
-    };
-
-    let mut image = RgbaImageBuffer::new(IMAGE_WIDTH, IMAGE_HEIGHT);
-    for x in 0..IMAGE_WIDTH {
-        for y in 0..IMAGE_HEIGHT {
-            image.put_pixel(x, y, color(x, y));
-        }
-    }
-
-    image
-}
-
 fn merge_images_slow(
     images_per_row: u32,
     total_images: u32,
@@ -38,9 +18,9 @@ fn merge_images_slow(
     padding_y: u32,
 ) -> RgbaImageBuffer {
     // Cieling division for total rows.
-    let total_rows = (total_images + images_per_row - 1) / images_per_row;
+    let total_rows = total_images.div_ceil(images_per_row);
 
-    let test_square = generate_test_square();
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
 
     let mut canvas = RgbaImageBuffer::new(
         IMAGE_WIDTH * images_per_row + (padding_x * (images_per_row - 1)),
@@ -82,7 +62,7 @@ fn test_slow_merge_padding() {
 
 #[test]
 fn test_push_merge() {
-    let test_square = generate_test_square();
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
     let slow_merge = merge_images_slow(10, TOTAL_IMAGES, 0, 0);
 
     let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
@@ -101,7 +81,7 @@ fn test_push_merge() {
 
 #[test]
 fn test_bulk_push_merge() {
-    let test_square = generate_test_square();
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
     let slow_merge = merge_images_slow(10, TOTAL_IMAGES, 0, 0);
 
     let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
@@ -117,7 +97,7 @@ fn test_bulk_push_merge() {
 
 #[test]
 fn test_push_merge_padding() {
-    let test_square: RgbaImageBuffer = generate_test_square();
+    let test_square: RgbaImageBuffer = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
     let slow_merge = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES, PADDING_X, PADDING_Y);
 
     let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
@@ -139,7 +119,7 @@ fn test_push_merge_padding() {
 
 #[test]
 fn test_bulk_push_merge_padding() {
-    let test_square = generate_test_square();
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
     let slow_merge = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES, PADDING_X, PADDING_Y);
 
     let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
@@ -159,7 +139,7 @@ fn test_bulk_push_merge_padding() {
 #[test]
 fn test_remove_image() {
     // 99 images on the slow merge should be equal to 100 images on the fast merge minus the 1 removed image.
-    let test_square = generate_test_square();
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
     let slow_merge = merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES - 1, 0, 0);
 
     let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
@@ -170,7 +150,7 @@ fn test_remove_image() {
     );
 
     merger.bulk_push(&vec![&test_square; TOTAL_IMAGES as usize]);
-    merger.remove_image(99);
+    merger.remove_image(99).unwrap();
 
     assert_eq!(merger.get_canvas(), &slow_merge);
 }
@@ -224,7 +204,7 @@ fn test_resizable_known_size_merger() {
     // Create an image that is twice the size of the original image. Using a known
     // resize function to ensure the image is resized correctly.
     let test_square = image::imageops::resize(
-        &generate_test_square().into_buffer(),
+        &RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT).into_buffer(),
         IMAGE_WIDTH * 2,
         IMAGE_HEIGHT * 2,
         image::imageops::FilterType::Nearest,
@@ -247,7 +227,7 @@ fn test_resizable_bulk_known_size_merger() {
 
     // Create an image that is twice the size of the original image.
     let test_square = image::imageops::resize(
-        &generate_test_square().into_buffer(),
+        &RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT).into_buffer(),
         IMAGE_WIDTH * 2,
         IMAGE_HEIGHT * 2,
         image::imageops::FilterType::Nearest,
@@ -257,3 +237,2877 @@ fn test_resizable_bulk_known_size_merger() {
     merger.bulk_push_resized(&vec![&Image::from(test_square); TOTAL_IMAGES as usize]);
     assert!(merger.get_num_images() == TOTAL_IMAGES);
 }
+
+#[test]
+fn test_builder_matches_new() {
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+
+    let merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        Some(padding),
+    );
+
+    let background = Rgba([255, 255, 255, 255]);
+    let built: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(IMAGES_PER_ROW)
+        .total_images(TOTAL_IMAGES)
+        .padding(padding)
+        .background(background)
+        .build();
+
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        built.get_canvas().dimensions()
+    );
+    assert_eq!(*built.get_canvas().get_pixel(0, 0), background);
+}
+
+#[test]
+fn test_get_canvas_mut() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        None,
+    );
+    merger.bulk_push(&[&test_square, &test_square]);
+
+    let border = Rgba([1, 2, 3, 4]);
+    merger
+        .get_canvas_mut()
+        .put_pixel(IMAGE_WIDTH + 5, 5, border);
+
+    assert_eq!(*merger.get_canvas().get_pixel(IMAGE_WIDTH + 5, 5), border);
+    assert_eq!(
+        merger.get_canvas().get_pixel(5, 5),
+        test_square.get_pixel(5, 5)
+    );
+}
+
+#[test]
+fn test_clear_reuses_merger() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let red_square =
+        RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255, 0, 0, 255]));
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        None,
+    );
+    merger.bulk_push(&vec![&test_square; TOTAL_IMAGES as usize]);
+    merger.clear();
+    assert_eq!(merger.get_num_images(), 0);
+
+    merger.bulk_push(&[&red_square; 15]);
+
+    let mut fresh: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        None,
+    );
+    fresh.bulk_push(&[&red_square; 15]);
+
+    assert_eq!(merger.get_canvas(), fresh.get_canvas());
+
+    // Only the canvas is cleared when its container is longer than it.
+    let mut buffer = vec![0xABu8; 13];
+    let mut merger: KnownSizeMerger<Luma<u8>, &mut [u8]> =
+        KnownSizeMerger::new_from_raw((2, 2), 1, 1, None, buffer.as_mut_slice()).unwrap();
+    merger.clear();
+    drop(merger);
+    assert_eq!(&buffer[..4], &[0; 4]);
+    assert_eq!(&buffer[4..], &[0xAB; 9]);
+}
+
+#[test]
+fn test_cell_rects() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        2,
+        6,
+        Some(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        }),
+    );
+    merger.bulk_push(&[&test_square; 5]);
+
+    let rects: Vec<_> = merger.cell_rects().collect();
+    assert_eq!(rects.len(), 5);
+
+    let (index, top_left, dimensions) = rects[2];
+    assert_eq!(index, 2);
+    assert_eq!(
+        top_left,
+        Point {
+            x: 0,
+            y: IMAGE_HEIGHT + PADDING_Y
+        }
+    );
+    assert_eq!(dimensions, (IMAGE_WIDTH, IMAGE_HEIGHT));
+}
+
+#[test]
+fn test_index_at() {
+    let merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        2,
+        6,
+        Some(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        }),
+    );
+
+    // Cell 3 is the second image on the second row.
+    let x = IMAGE_WIDTH + PADDING_X + 5;
+    let y = IMAGE_HEIGHT + PADDING_Y + 5;
+    assert_eq!(merger.index_at(x, y), Some(3));
+    assert_eq!(merger.index_at(0, 0), Some(0));
+
+    // The gap between the first and second columns.
+    assert_eq!(merger.index_at(IMAGE_WIDTH + PADDING_X / 2, 5), None);
+
+    // Past the right and bottom edges of the canvas.
+    let (width, height) = merger.get_canvas().dimensions();
+    assert_eq!(merger.index_at(width, 5), None);
+    assert_eq!(merger.index_at(5, height), None);
+}
+
+#[test]
+fn test_paste_canvas() {
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+    let images: Vec<RgbaImageBuffer> = (0..8u8)
+        .map(|i| {
+            RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([i * 30, 0, 0, 255]))
+        })
+        .collect();
+
+    // The left and right halves of a 4x2 grid, built as separate 2x2 grids.
+    let mut left: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, Some(padding));
+    left.bulk_push(&[&images[0], &images[1], &images[4], &images[5]]);
+    let mut right: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, Some(padding));
+    right.bulk_push(&[&images[2], &images[3], &images[6], &images[7]]);
+
+    let mut master: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 4, 8, Some(padding));
+    master
+        .paste_canvas(left.get_canvas(), Point { x: 0, y: 0 })
+        .unwrap();
+    master
+        .paste_canvas(
+            right.get_canvas(),
+            Point {
+                x: 2 * (IMAGE_WIDTH + PADDING_X),
+                y: 0,
+            },
+        )
+        .unwrap();
+
+    let mut direct: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 4, 8, Some(padding));
+    direct.bulk_push(&images.iter().collect::<Vec<_>>());
+
+    assert!(master
+        .get_canvas()
+        .pixels()
+        .eq(direct.get_canvas().pixels()));
+
+    // The right half does not fit any further to the right.
+    assert!(matches!(
+        master.paste_canvas(
+            right.get_canvas(),
+            Point {
+                x: 2 * (IMAGE_WIDTH + PADDING_X) + 1,
+                y: 0,
+            },
+        ),
+        Err(MergerError::OutOfCanvas { .. })
+    ));
+}
+
+#[test]
+fn test_into_canvas_borrowed_container() {
+    let mut buffer = vec![0u8; 4 * 2 * 2 * 3];
+    let mut tile_buffer = vec![255u8; 2 * 2 * 3];
+    let tile: Image<Rgb<u8>, ImageBuffer<Rgb<u8>, &mut [u8]>> =
+        Image::new_from_raw(2, 2, tile_buffer.as_mut_slice()).unwrap();
+
+    let mut merger: KnownSizeMerger<Rgb<u8>, &mut [u8]> =
+        KnownSizeMerger::new_from_raw((2, 2), 2, 2, None, buffer.as_mut_slice()).unwrap();
+    merger.push(&tile);
+
+    // The canvas hands back the same borrowed slice it was built from.
+    let container: &mut [u8] = merger.into_canvas().into_buffer().into_raw();
+    assert_eq!(container.len(), 4 * 2 * 2 * 3);
+    assert_eq!(&container[..6], &[255; 6]);
+    assert_eq!(&container[6..12], &[0; 6]);
+    assert_eq!(&buffer[12..18], &[255; 6]);
+}
+
+#[test]
+fn test_grow() {
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+    let images: Vec<RgbaImageBuffer> = (0..20u8)
+        .map(|i| {
+            RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([i * 10, 0, 0, 255]))
+        })
+        .collect();
+    let images: Vec<&RgbaImageBuffer> = images.iter().collect();
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 5, 10, Some(padding));
+    merger.bulk_push(&images[..10]);
+    merger.grow(10);
+    merger.bulk_push(&images[10..]);
+
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 5, 20, Some(padding));
+    expected.bulk_push(&images);
+
+    assert_eq!(merger.get_num_images(), 20);
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        expected.get_canvas().dimensions()
+    );
+    assert!(merger
+        .get_canvas()
+        .pixels()
+        .eq(expected.get_canvas().pixels()));
+}
+
+#[test]
+fn test_bulk_push_resized_borrowed() {
+    // The images are owned elsewhere and only borrowed by the merger.
+    let images: Vec<RgbaImageBuffer> = (0..6u8)
+        .map(|i| RgbaImageBuffer::new_from_pixel(250 + i as u32, 300, Rgba([i * 40, 0, 0, 255])))
+        .collect();
+    let borrowed: Vec<&RgbaImageBuffer> = images.iter().collect();
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 6, None);
+    merger.bulk_push_resized(&borrowed);
+
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 6, None);
+    for image in &images {
+        expected.push_resized(image);
+    }
+
+    assert_eq!(images.len(), 6);
+    assert!(merger
+        .get_canvas()
+        .pixels()
+        .eq(expected.get_canvas().pixels()));
+}
+
+#[test]
+fn test_bulk_push_deduped() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let padding = Some(Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    });
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        padding,
+    );
+    merger.bulk_push_deduped(&[&test_square; TOTAL_IMAGES as usize]);
+
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        padding,
+    );
+    expected.bulk_push(&[&test_square; TOTAL_IMAGES as usize]);
+
+    assert_eq!(merger.get_num_images(), TOTAL_IMAGES);
+    assert!(merger
+        .get_canvas()
+        .pixels()
+        .eq(expected.get_canvas().pixels()));
+}
+
+#[test]
+fn test_bulk_push_deduped_overlapping() {
+    let red = RgbaImageBuffer::new_from_pixel(10, 10, Rgba([255, 0, 0, 255]));
+    let green = RgbaImageBuffer::new_from_pixel(10, 10, Rgba([0, 255, 0, 255]));
+    let place = |index: u32| Point {
+        x: index * 5,
+        y: index * 5,
+    };
+
+    // Each image overlaps the one before it, so the duplicate red must not be copied from the cell green pasted over.
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::with_placement((10, 10), (30, 30), 3, place);
+    merger.bulk_push_deduped(&[&red, &green, &red]);
+
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::with_placement((10, 10), (30, 30), 3, place);
+    expected.bulk_push(&[&red, &green, &red]);
+
+    assert_eq!(merger.get_num_images(), 3);
+    assert!(merger
+        .get_canvas()
+        .pixels()
+        .eq(expected.get_canvas().pixels()));
+    assert_eq!(
+        *merger.get_canvas().get_pixel(19, 19),
+        Rgba([255, 0, 0, 255])
+    );
+}
+
+#[test]
+fn test_bulk_push_deduped_dimension_mismatch() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let small = RgbaImageBuffer::new(IMAGE_WIDTH / 2, IMAGE_HEIGHT);
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        merger.bulk_push_deduped(&[&test_square, &small]);
+    }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_fill_background_checkerboard() {
+    let light = Rgba([204, 204, 204, 255]);
+    let dark = Rgba([153, 153, 153, 255]);
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((20, 20), 2, 4, None);
+    merger.fill_background_checkerboard(light, dark, 8);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(0, 0), light);
+    assert_eq!(*canvas.get_pixel(7, 7), light);
+    assert_eq!(*canvas.get_pixel(8, 0), dark);
+    assert_eq!(*canvas.get_pixel(0, 8), dark);
+    assert_eq!(*canvas.get_pixel(8, 8), light);
+    assert_eq!(*canvas.get_pixel(39, 39), light);
+
+    // Rows past the canvas in a longer container are left untouched.
+    let mut buffer = vec![0u8; 2 * 2 * 4 * 3];
+    let mut merger: KnownSizeMerger<Rgba<u8>, &mut [u8]> =
+        KnownSizeMerger::new_from_raw((2, 2), 1, 1, None, buffer.as_mut_slice()).unwrap();
+    merger.fill_background_checkerboard(light, dark, 1);
+    drop(merger);
+    assert_eq!(
+        &buffer[..16],
+        &[204, 204, 204, 255, 153, 153, 153, 255, 153, 153, 153, 255, 204, 204, 204, 255]
+    );
+    assert!(buffer[16..].iter().all(|&value| value == 0));
+}
+
+#[test]
+fn test_as_subpixels() {
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((4, 3), 2, 4, None);
+    merger.push(&RgbaImageBuffer::new_from_pixel(4, 3, Rgba([1, 2, 3, 4])));
+
+    let (width, height) = merger.get_canvas().dimensions();
+    let subpixels = merger.as_subpixels();
+    assert_eq!(subpixels.len(), (width * height * 4) as usize);
+    assert_eq!(&subpixels[..4], &[1, 2, 3, 4]);
+
+    merger.as_subpixels_mut()[..4].copy_from_slice(&[5, 6, 7, 8]);
+    assert_eq!(*merger.get_canvas().get_pixel(0, 0), Rgba([5, 6, 7, 8]));
+
+    // A container longer than the canvas is cut down to the canvas.
+    let mut oversized: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new_from_raw(
+        (4, 3),
+        2,
+        4,
+        None,
+        vec![0u8; (width * height * 4) as usize + 16],
+    )
+    .unwrap();
+    assert_eq!(
+        oversized.as_subpixels().len(),
+        (width * height * 4) as usize
+    );
+    assert_eq!(
+        oversized.as_subpixels_mut().len(),
+        (width * height * 4) as usize
+    );
+    assert_eq!(oversized.memory_footprint(), (width * height * 4) as usize);
+}
+
+#[test]
+fn test_merger_eq_and_debug() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let build = || -> KnownSizeMerger<Rgba<u8>, _> {
+        let mut merger = KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+        merger.bulk_push(&[&test_square; 3]);
+        merger
+    };
+
+    let merger = build();
+    assert_eq!(merger, build());
+
+    let mut different = build();
+    different.push(&RgbaImageBuffer::new_from_pixel(
+        IMAGE_WIDTH,
+        IMAGE_HEIGHT,
+        Rgba([1, 2, 3, 4]),
+    ));
+    let mut other = build();
+    other.push(&test_square);
+    assert_ne!(different, other);
+
+    let debug = format!("{:?}", merger);
+    assert!(debug.starts_with("KnownSizeMerger"));
+    assert!(debug.contains("num_images: 3"));
+    assert!(debug.contains("total_rows: 2"));
+}
+
+#[test]
+fn test_save_and_load_state() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let padding = Some(Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    });
+    let half = (TOTAL_IMAGES / 2) as usize;
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        padding,
+    );
+    merger.bulk_push(&vec![&test_square; half]);
+
+    let mut state = Vec::new();
+    merger.save_state(&mut state).unwrap();
+
+    let mut restored: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::load_state(state.as_slice()).unwrap();
+    assert_eq!(restored, merger);
+    restored.bulk_push(&vec![&test_square; half]);
+
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        padding,
+    );
+    expected.bulk_push(&[&test_square; TOTAL_IMAGES as usize]);
+    assert_eq!(restored, expected);
+
+    // State saved for one pixel type can not be loaded as another.
+    assert!(KnownSizeMerger::<Rgba<u16>, _>::load_state(state.as_slice()).is_err());
+    assert!(KnownSizeMerger::<Rgb<u8>, _>::load_state(state.as_slice()).is_err());
+    assert!(KnownSizeMerger::<Rgba<u8>, _>::load_state(&b"nope"[..]).is_err());
+}
+
+#[test]
+fn test_load_state_corrupted_header() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    merger.bulk_push(&[&test_square; 2]);
+
+    let mut state = Vec::new();
+    merger.save_state(&mut state).unwrap();
+
+    // The header starts after the magic number, version, and 4 flag bytes, and holds little-endian u32s.
+    let corrupt = |field: usize, value: u32| {
+        let mut state = state.clone();
+        let offset = 9 + field * 4;
+        state[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        KnownSizeMerger::<Rgba<u8>, Vec<u8>>::load_state(state.as_slice()).map(|_| ())
+    };
+    let kind = |result: std::io::Result<()>| result.unwrap_err().kind();
+
+    // The last pasted index is past the end of the canvas.
+    assert_eq!(kind(corrupt(5, 100)), std::io::ErrorKind::InvalidData);
+    assert_eq!(kind(corrupt(5, 4)), std::io::ErrorKind::InvalidData);
+    assert!(corrupt(5, 3).is_ok());
+    // The number of images disagrees with the occupied cells.
+    assert_eq!(kind(corrupt(4, 3)), std::io::ErrorKind::InvalidData);
+    // A huge canvas is rejected once the reader runs out, rather than being allocated up front.
+    assert!(corrupt(0, 1 << 30).is_err());
+    assert!(corrupt(3, u32::MAX).is_err());
+}
+
+#[test]
+fn test_encode_animation() {
+    use image::AnimationDecoder;
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((8, 8), 3, 3, None);
+    for i in 0..3u8 {
+        merger.push(&RgbaImageBuffer::new_from_pixel(
+            8,
+            8,
+            Rgba([i * 100, 0, 0, 255]),
+        ));
+    }
+
+    let encoded = merger.encode_animation(50).unwrap();
+    let decoder = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(encoded)).unwrap();
+    let frames = decoder.into_frames().collect_frames().unwrap();
+
+    assert_eq!(frames.len(), 3);
+    for (i, frame) in frames.iter().enumerate() {
+        assert_eq!(frame.delay().numer_denom_ms(), (50, 1));
+        assert_eq!(frame.buffer().dimensions(), (8, 8));
+        assert_eq!(
+            *frame.buffer().get_pixel(4, 4),
+            Rgba([i as u8 * 100, 0, 0, 255])
+        );
+    }
+}
+
+#[test]
+fn test_push_iter() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    merger
+        .push_iter((0..3).map(|_| RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT)))
+        .unwrap();
+    assert_eq!(merger.get_num_images(), 3);
+
+    // Only the first image past the end of the canvas may be pulled.
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    let images = (0..).map(|i| {
+        assert!(
+            i <= 4,
+            "The iterator was advanced past the capacity of the canvas!"
+        );
+        RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT)
+    });
+    assert!(matches!(
+        merger.push_iter(images),
+        Err(MergerError::IndexOutOfBounds {
+            index: 4,
+            capacity: 4
+        })
+    ));
+
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    expected.bulk_push(&[&test_square; 4]);
+    assert_eq!(merger, expected);
+}
+
+#[test]
+fn test_push_skip() {
+    let background = Rgba([0, 0, 255, 255]);
+    let tile = RgbaImageBuffer::new_from_pixel(10, 10, Rgba([255, 0, 0, 255]));
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::builder()
+        .image_dimensions((10, 10))
+        .images_per_row(3)
+        .total_images(3)
+        .background(background)
+        .build();
+    merger.push(&tile);
+    merger.push_skip(1);
+    merger.push(&tile);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(5, 5), Rgba([255, 0, 0, 255]));
+    assert_eq!(*canvas.get_pixel(15, 5), background);
+    assert_eq!(*canvas.get_pixel(25, 5), Rgba([255, 0, 0, 255]));
+
+    assert_eq!(merger.get_num_images(), 2);
+    let indices: Vec<u32> = merger.cell_rects().map(|(index, _, _)| index).collect();
+    assert_eq!(indices, vec![0, 2]);
+}
+
+#[test]
+fn test_try_new_invalid_parameters() {
+    let invalid = [
+        ((0, IMAGE_HEIGHT), IMAGES_PER_ROW, TOTAL_IMAGES),
+        ((IMAGE_WIDTH, 0), IMAGES_PER_ROW, TOTAL_IMAGES),
+        ((IMAGE_WIDTH, IMAGE_HEIGHT), 0, TOTAL_IMAGES),
+        ((IMAGE_WIDTH, IMAGE_HEIGHT), IMAGES_PER_ROW, 0),
+    ];
+
+    for (image_dimensions, images_per_row, total_images) in invalid {
+        let result = KnownSizeMerger::<Rgba<u8>, _>::try_new(
+            image_dimensions,
+            images_per_row,
+            total_images,
+            None,
+        );
+        assert!(matches!(result, Err(MergerError::InvalidLayout { .. })));
+    }
+
+    let merger = KnownSizeMerger::<Rgba<u8>, _>::try_new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        None,
+    );
+    assert!(merger.is_ok());
+}
+
+#[test]
+fn test_try_new_canvas_too_large() {
+    // 10,000 images of 1,000,000 pixels wide in one row does not fit in a u32.
+    let result = KnownSizeMerger::<Rgba<u8>, _>::try_new((1_000_000, 1), 10_000, 10_000, None);
+    assert!(matches!(result, Err(MergerError::InvalidLayout { .. })));
+
+    let result =
+        KnownSizeMerger::<Rgba<u8>, _>::try_new((1, 1), 1, u32::MAX, Some(Padding { x: 0, y: 1 }));
+    assert!(matches!(result, Err(MergerError::InvalidLayout { .. })));
+
+    let merger = KnownSizeMerger::<Rgba<u8>, Vec<u8>>::new_from_raw(
+        (1_000_000, 1),
+        10_000,
+        10_000,
+        None,
+        Vec::new(),
+    );
+    assert!(merger.is_none());
+}
+
+#[test]
+#[should_panic(expected = "The merger could not be created with the given parameters!")]
+fn test_new_zero_images_per_row() {
+    KnownSizeMerger::<Rgba<u8>, _>::new((IMAGE_WIDTH, IMAGE_HEIGHT), 0, TOTAL_IMAGES, None);
+}
+
+#[test]
+fn test_push_centered() {
+    let fill = Rgba([0, 0, 255, 255]);
+    let tile = RgbaImageBuffer::new_from_pixel(50, 50, Rgba([255, 0, 0, 255]));
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 2, None);
+    merger.push_centered(&tile, fill).unwrap();
+    merger.push_centered(&tile, fill).unwrap();
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(24, 50), fill);
+    assert_eq!(*canvas.get_pixel(25, 25), Rgba([255, 0, 0, 255]));
+    assert_eq!(*canvas.get_pixel(74, 74), Rgba([255, 0, 0, 255]));
+    assert_eq!(*canvas.get_pixel(75, 50), fill);
+    assert_eq!(*canvas.get_pixel(50, 24), fill);
+    assert_eq!(
+        *canvas.get_pixel(IMAGE_WIDTH + 25, 25),
+        Rgba([255, 0, 0, 255])
+    );
+    assert_eq!(merger.get_num_images(), 2);
+
+    let too_large = RgbaImageBuffer::new(IMAGE_WIDTH + 1, 50);
+    assert!(matches!(
+        merger.push_centered(&too_large, fill),
+        Err(MergerError::DimensionMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_to_luma() {
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((2, 2), 3, 3, None);
+    merger.bulk_push(&[
+        &RgbaImageBuffer::new_from_pixel(2, 2, Rgba([255, 0, 0, 255])),
+        &RgbaImageBuffer::new_from_pixel(2, 2, Rgba([0, 255, 0, 255])),
+        &RgbaImageBuffer::new_from_pixel(2, 2, Rgba([100, 150, 200, 0])),
+    ]);
+
+    let luma = merger.to_luma();
+    assert_eq!(luma.dimensions(), (6, 2));
+    assert_eq!(*luma.get_pixel(0, 0), Luma([76])); // 0.299 * 255
+    assert_eq!(*luma.get_pixel(2, 0), Luma([150])); // 0.587 * 255
+    assert_eq!(*luma.get_pixel(4, 0), Luma([141])); // 0.299 * 100 + 0.587 * 150 + 0.114 * 200
+
+    let mut gray: KnownSizeMerger<Luma<u8>, _> = KnownSizeMerger::new((2, 2), 1, 1, None);
+    gray.push(&BufferedImage::new_from_pixel(2, 2, Luma([77])));
+    assert_eq!(*gray.to_luma().get_pixel(1, 1), Luma([77]));
+}
+
+#[test]
+fn test_thumbnail() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 10, 100, None);
+    merger.bulk_push(&[&test_square; 100]);
+    assert_eq!(merger.get_canvas().dimensions(), (1000, 1000));
+
+    let thumbnail = merger.thumbnail(64);
+    assert_eq!(thumbnail.dimensions(), (64, 64));
+
+    // A wide canvas keeps its 2:1 aspect ratio.
+    let mut wide: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 10, 50, None);
+    wide.bulk_push(&[&test_square; 50]);
+    assert_eq!(wide.thumbnail(100).dimensions(), (100, 50));
+}
+
+#[test]
+fn test_from_sheet() {
+    let padding = Some(Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    });
+    let tiles: Vec<RgbaImageBuffer> = (0..4u8)
+        .map(|i| {
+            RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([i * 60, 0, 0, 255]))
+        })
+        .collect();
+
+    let mut sheet: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, padding);
+    sheet.bulk_push(&tiles.iter().collect::<Vec<_>>());
+
+    let merger =
+        KnownSizeMerger::from_sheet(sheet.get_canvas(), IMAGE_WIDTH, IMAGE_HEIGHT, 2, padding);
+    assert_eq!(merger, sheet);
+
+    // The same sheet laid out as a single row.
+    let strip =
+        KnownSizeMerger::from_sheet(sheet.get_canvas(), IMAGE_WIDTH, IMAGE_HEIGHT, 4, padding);
+    assert_eq!(strip.get_num_images(), 4);
+    assert_eq!(
+        *strip
+            .get_canvas()
+            .get_pixel(3 * (IMAGE_WIDTH + PADDING_X), 0),
+        Rgba([180, 0, 0, 255])
+    );
+}
+
+#[test]
+fn test_push_indexed() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 9, None);
+
+    for expected in 0..3 {
+        assert_eq!(merger.push_indexed(&test_square), expected);
+    }
+    assert_eq!(merger.bulk_push_indexed(&[&test_square; 4]), 3..7);
+
+    merger.push_skip(1);
+    assert_eq!(merger.push_indexed(&test_square), 8);
+
+    // A cell filled out of order is skipped over, but still falls within the range.
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 9, None);
+    merger.push_at(1, &test_square).unwrap();
+    assert_eq!(merger.bulk_push_indexed(&[&test_square; 2]), 0..3);
+    assert_eq!(merger.bulk_push_indexed::<Vec<u8>>(&[]), 3..3);
+}
+
+#[test]
+fn test_with_placement() {
+    let red = RgbaImageBuffer::new_from_pixel(10, 10, Rgba([255, 0, 0, 255]));
+    let green = RgbaImageBuffer::new_from_pixel(10, 10, Rgba([0, 255, 0, 255]));
+
+    // Place each image one step further along the diagonal.
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::with_placement((10, 10), (40, 40), 4, |index| Point {
+            x: index * 15,
+            y: index * 15,
+        });
+    merger.bulk_push(&[&red, &green]);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+    assert_eq!(*canvas.get_pixel(15, 15), Rgba([0, 255, 0, 255]));
+    assert_eq!(*canvas.get_pixel(24, 24), Rgba([0, 255, 0, 255]));
+    assert_eq!(*canvas.get_pixel(12, 12), Rgba([0, 0, 0, 0]));
+    assert_eq!(merger.index_at(20, 20), Some(1));
+    assert_eq!(merger.index_at(12, 12), None);
+
+    // The third image would run off the edge of the canvas.
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        merger.push_at(3, &red).unwrap();
+    }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_from_canvas() {
+    let red = Rgba([255, 0, 0, 255]);
+    let tile = RgbaImageBuffer::new_from_pixel(10, 10, Rgba([0, 0, 255, 255]));
+
+    let canvas = RgbaImageBuffer::new_from_pixel(30, 25, red);
+    let mut merger = KnownSizeMerger::from_canvas(canvas, (10, 10), 2, 4, None).unwrap();
+    merger.push(&tile);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.dimensions(), (30, 25));
+    assert_eq!(*canvas.get_pixel(5, 5), Rgba([0, 0, 255, 255]));
+    assert_eq!(*canvas.get_pixel(15, 5), red);
+    assert_eq!(*canvas.get_pixel(29, 24), red);
+
+    // A 2x2 grid of 10x10 images does not fit on a 15x15 canvas.
+    let small = RgbaImageBuffer::new_from_pixel(15, 15, red);
+    assert!(KnownSizeMerger::from_canvas(small, (10, 10), 2, 4, None).is_none());
+
+    // The canvas is larger than the grid, so saving keeps all of it.
+    let mut state = Vec::new();
+    merger.save_state(&mut state).unwrap();
+    let restored: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::load_state(state.as_slice()).unwrap();
+    assert_eq!(restored, merger);
+
+    // Growing to 3 rows needs a 20x30 grid, so only the height of the canvas grows.
+    merger.grow(2);
+    merger.push(&tile);
+    let canvas = merger.get_canvas();
+    assert_eq!(canvas.dimensions(), (30, 30));
+    assert_eq!(*canvas.get_pixel(5, 5), Rgba([0, 0, 255, 255]));
+    assert_eq!(*canvas.get_pixel(15, 5), Rgba([0, 0, 255, 255]));
+    assert_eq!(*canvas.get_pixel(29, 24), red);
+    assert_eq!(*canvas.get_pixel(29, 29), Rgba([0, 0, 0, 0]));
+}
+
+#[test]
+fn test_margin() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(2)
+        .total_images(4)
+        .padding(padding)
+        .margin(Point { x: 20, y: 20 })
+        .build();
+    merger.bulk_push(&[&test_square; 4]);
+
+    let flush: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, Some(padding));
+    let (width, height) = flush.get_canvas().dimensions();
+    assert_eq!(merger.get_canvas().dimensions(), (width + 40, height + 40));
+
+    let (_, top_left, _) = merger.cell_rects().next().unwrap();
+    assert_eq!(top_left, Point { x: 20, y: 20 });
+    assert_eq!(*merger.get_canvas().get_pixel(19, 19), Rgba([0, 0, 0, 0]));
+    assert_eq!(
+        *merger.get_canvas().get_pixel(20, 20),
+        *test_square.get_pixel(0, 0)
+    );
+
+    assert_eq!(merger.index_at(10, 10), None);
+    assert_eq!(merger.index_at(20, 20), Some(0));
+    assert_eq!(merger.index_at(width + 30, 30), None);
+}
+
+#[test]
+fn test_remaining_capacity() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 5, None);
+
+    // 5 images over 3 per row needs 2 rows, but the blank cell at the end of the last row can not be used.
+    assert_eq!(merger.capacity(), 5);
+    assert_eq!(merger.remaining_capacity(), 5);
+    assert!(!merger.is_full());
+
+    merger.push(&test_square);
+    assert_eq!(merger.remaining_capacity(), 4);
+    merger.bulk_push(&[&test_square; 3]);
+    assert_eq!(merger.remaining_capacity(), 1);
+    assert!(!merger.is_full());
+
+    merger.push(&test_square);
+    assert_eq!(merger.remaining_capacity(), 0);
+    assert!(merger.is_full());
+    assert_eq!(merger.capacity(), 5);
+}
+
+#[test]
+fn test_into_iter() {
+    let tiles: Vec<RgbaImageBuffer> = (0..4u8)
+        .map(|i| {
+            RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([i * 60, 255, 0, 255]))
+        })
+        .collect();
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        3,
+        6,
+        Some(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        }),
+    );
+    merger.bulk_push(&tiles.iter().collect::<Vec<_>>());
+
+    let borrowed: Vec<RgbaImageBuffer> = merger.iter().collect();
+    assert_eq!(borrowed.len(), 4);
+    assert!(borrowed.iter().eq(tiles.iter()));
+
+    let mut count = 0;
+    for (image, tile) in merger.into_iter().zip(&tiles) {
+        assert_eq!(image, *tile);
+        count += 1;
+    }
+    assert_eq!(count, 4);
+}
+
+#[test]
+fn test_scatter_push() {
+    let mut merger = KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 9, None);
+    let images: Vec<RgbaImageBuffer> = [255, 128, 64]
+        .into_iter()
+        .map(|value| {
+            RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([value, 0, 0, 255]))
+        })
+        .collect();
+
+    merger
+        .scatter_push(&[(3, &images[0]), (0, &images[1]), (7, &images[2])])
+        .unwrap();
+    assert_eq!(merger.get_num_images(), 3);
+
+    let canvas = merger.get_canvas();
+    let pixel_in = |index: u32| {
+        let x = (index % 3) * IMAGE_WIDTH + IMAGE_WIDTH / 2;
+        let y = (index / 3) * IMAGE_HEIGHT + IMAGE_HEIGHT / 2;
+        *canvas.get_pixel(x, y)
+    };
+    for index in 0..9 {
+        let expected = match index {
+            3 => Rgba([255, 0, 0, 255]),
+            0 => Rgba([128, 0, 0, 255]),
+            7 => Rgba([64, 0, 0, 255]),
+            _ => Rgba([0, 0, 0, 0]),
+        };
+        assert_eq!(pixel_in(index), expected, "cell {index}");
+    }
+}
+
+#[test]
+fn test_scatter_push_rejects_invalid_indices() {
+    let mut merger = KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 9, None);
+    let image = RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255, 0, 0, 255]));
+
+    assert!(matches!(
+        merger.scatter_push(&[(1, &image), (9, &image)]),
+        Err(MergerError::IndexOutOfBounds {
+            index: 9,
+            capacity: 9
+        })
+    ));
+    assert!(matches!(
+        merger.scatter_push(&[(4, &image), (2, &image), (4, &image)]),
+        Err(MergerError::DuplicateIndex { index: 4 })
+    ));
+    let small = RgbaImageBuffer::new(IMAGE_WIDTH / 2, IMAGE_HEIGHT);
+    assert!(matches!(
+        merger.scatter_push(&[(4, &image), (2, &small)]),
+        Err(MergerError::DimensionMismatch { index: Some(1), .. })
+    ));
+
+    // Nothing is pasted when any index is invalid.
+    assert_eq!(merger.get_num_images(), 0);
+    assert!(merger.get_canvas().pixels().all(|p| p[3] == 0));
+}
+
+#[test]
+fn test_scatter_push_overlapping() {
+    let red = RgbaImageBuffer::new_from_pixel(10, 10, Rgba([255, 0, 0, 255]));
+    let green = RgbaImageBuffer::new_from_pixel(10, 10, Rgba([0, 255, 0, 255]));
+
+    // Both cells share the same spot, so the image given last must end up on top.
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::with_placement((10, 10), (20, 20), 2, |index| Point {
+            x: index * 5,
+            y: index * 5,
+        });
+    merger.scatter_push(&[(1, &red), (0, &green)]).unwrap();
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(7, 7), Rgba([0, 255, 0, 255]));
+    assert_eq!(*canvas.get_pixel(12, 12), Rgba([255, 0, 0, 255]));
+}
+
+#[test]
+fn test_partial_last_row() {
+    // 7 images at 3 per row need 3 rows, the last of which has 2 blank cells.
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 7, None);
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        (IMAGE_WIDTH * 3, IMAGE_HEIGHT * 3)
+    );
+    assert_eq!(merger.capacity(), 7);
+
+    let image = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    assert!(matches!(
+        merger.push_at(7, &image),
+        Err(MergerError::IndexOutOfBounds {
+            index: 7,
+            capacity: 7
+        })
+    ));
+    assert!(merger.remove_image(8).is_err());
+    assert_eq!(merger.index_at(IMAGE_WIDTH + 1, IMAGE_HEIGHT * 2 + 1), None);
+    assert_eq!(merger.index_at(1, IMAGE_HEIGHT * 2 + 1), Some(6));
+
+    for _ in 0..7 {
+        merger.push(&image);
+    }
+    assert!(merger.is_full());
+    assert_eq!(merger.get_num_images(), 7);
+}
+
+#[test]
+fn test_memory_mapped_canvas() {
+    let path = std::env::temp_dir().join(format!("image-merger-mmap-{}.raw", std::process::id()));
+    let (width, height) = (IMAGE_WIDTH * 2, IMAGE_HEIGHT * 2);
+    let len = (width * height * 4) as usize;
+
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&path)
+        .unwrap();
+    file.set_len(len as u64).unwrap();
+
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    {
+        let mut mmap = unsafe { memmap2::MmapMut::map_mut(&file) }.unwrap();
+        let mut merger: KnownSizeMerger<Rgba<u8>, &mut [u8]> =
+            KnownSizeMerger::new_from_raw((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None, &mut mmap[..])
+                .unwrap();
+        merger.push(&test_square);
+        merger.push(&RgbaImageBuffer::new_from_pixel(
+            IMAGE_WIDTH,
+            IMAGE_HEIGHT,
+            Rgba([255, 255, 255, 255]),
+        ));
+        drop(merger);
+        mmap.flush().unwrap();
+    }
+
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let canvas = RgbaImageBuffer::new_from_raw(width, height, bytes).unwrap();
+    for (x, y, pixel) in test_square.enumerate_pixels() {
+        assert_eq!(canvas.get_pixel(x, y), pixel);
+        assert_eq!(*canvas.get_pixel(x + IMAGE_WIDTH, y), Rgba([255; 4]));
+        assert_eq!(*canvas.get_pixel(x, y + IMAGE_HEIGHT), Rgba([0; 4]));
+    }
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_bulk_push_independent_of_thread_count() {
+    let images: Vec<RgbaImageBuffer> = (0..TOTAL_IMAGES)
+        .map(|i| {
+            RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([i as u8, 0, 0, 255]))
+        })
+        .collect();
+    let images: Vec<&RgbaImageBuffer> = images.iter().collect();
+
+    let merge = |num_threads: usize, overlapping: bool| {
+        let thread_pool = std::sync::Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .unwrap(),
+        );
+        let mut merger: KnownSizeMerger<Rgba<u8>, _> = if overlapping {
+            // Every image overlaps the previous one by half.
+            KnownSizeMerger::with_placement(
+                (IMAGE_WIDTH, IMAGE_HEIGHT),
+                (IMAGE_WIDTH * (TOTAL_IMAGES + 1) / 2, IMAGE_HEIGHT),
+                TOTAL_IMAGES,
+                |index| Point {
+                    x: index * IMAGE_WIDTH / 2,
+                    y: 0,
+                },
+            )
+        } else {
+            KnownSizeMerger::new(
+                (IMAGE_WIDTH, IMAGE_HEIGHT),
+                IMAGES_PER_ROW,
+                TOTAL_IMAGES,
+                Some(Padding {
+                    x: PADDING_X,
+                    y: PADDING_Y,
+                }),
+            )
+        }
+        .with_thread_pool(thread_pool)
+        .with_sequential_paste_threshold(0);
+        merger.bulk_push(&images);
+        merger.into_canvas().into_buffer().into_raw()
+    };
+
+    for overlapping in [false, true] {
+        let canvases: Vec<Vec<u8>> = [1, 2, 8]
+            .into_iter()
+            .map(|num_threads| merge(num_threads, overlapping))
+            .collect();
+        assert!(canvases[0] == canvases[1]);
+        assert!(canvases[0] == canvases[2]);
+    }
+
+    // Later images are drawn over earlier ones where they overlap.
+    let canvas = RgbaImageBuffer::new_from_raw(
+        IMAGE_WIDTH * (TOTAL_IMAGES + 1) / 2,
+        IMAGE_HEIGHT,
+        merge(8, true),
+    )
+    .unwrap();
+    for i in 0..TOTAL_IMAGES {
+        let x = i * IMAGE_WIDTH / 2 + 1;
+        assert_eq!(*canvas.get_pixel(x, 0), Rgba([i as u8, 0, 0, 255]));
+    }
+}
+
+#[test]
+fn test_premultiplied_round_trip() {
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((2, 1), 2, 2, None);
+    merger.push(&RgbaImageBuffer::new_from_pixel(
+        2,
+        1,
+        Rgba([200, 100, 50, 128]),
+    ));
+    merger.push(&RgbaImageBuffer::new_from_pixel(
+        2,
+        1,
+        Rgba([10, 20, 30, 255]),
+    ));
+
+    let premultiplied = merger.to_premultiplied();
+    assert_eq!(*premultiplied.get_pixel(0, 0), Rgba([100, 50, 25, 128]));
+    assert_eq!(*premultiplied.get_pixel(2, 0), Rgba([10, 20, 30, 255]));
+
+    let restored = RgbaImageBuffer::from_premultiplied(&premultiplied);
+    for (restored, original) in restored.pixels().zip(merger.get_canvas().pixels()) {
+        for (restored, original) in restored.0.iter().zip(original.0) {
+            assert!(restored.abs_diff(original) <= 1, "{restored} != {original}");
+        }
+    }
+}
+
+#[test]
+fn test_remove_images() {
+    let images: Vec<RgbaImageBuffer> = (0..12u8)
+        .map(|i| RgbaImageBuffer::new_from_pixel(10, 10, Rgba([i * 20, 0, 0, 255])))
+        .collect();
+    let removed = [1, 5, 9];
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((10, 10), 4, 12, None);
+    merger.bulk_push(&images.iter().collect::<Vec<_>>());
+    merger.remove_images(&removed).unwrap();
+    assert_eq!(merger.get_num_images(), 9);
+
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((10, 10), 4, 12, None);
+    for (index, image) in images.iter().enumerate() {
+        if removed.contains(&(index as u32)) {
+            expected.push_skip(1);
+        } else {
+            expected.push(image);
+        }
+    }
+    assert_eq!(merger.get_canvas(), expected.get_canvas());
+
+    assert!(matches!(
+        merger.remove_images(&[0, 12]),
+        Err(MergerError::IndexOutOfBounds {
+            index: 12,
+            capacity: 12
+        })
+    ));
+    assert!(matches!(
+        merger.remove_images(&[0, 3, 0]),
+        Err(MergerError::DuplicateIndex { index: 0 })
+    ));
+    assert_eq!(merger.get_num_images(), 9);
+}
+
+#[test]
+fn test_new_from_raw_checked() {
+    // 2 rows of 3 10x10 Rgb images need 30 * 20 * 3 subpixels.
+    let needed = 30 * 20 * 3;
+    let merger = KnownSizeMerger::<Rgb<u8>, _>::new_from_raw_checked(
+        (10, 10),
+        3,
+        6,
+        None,
+        vec![0; needed - 1],
+    );
+    assert!(matches!(
+        merger,
+        Err(MergerError::ContainerTooSmall {
+            needed: 1800,
+            got: 1799
+        })
+    ));
+
+    let merger =
+        KnownSizeMerger::<Rgb<u8>, _>::new_from_raw_checked((10, 10), 3, 6, None, vec![0; needed]);
+    assert_eq!(merger.unwrap().get_canvas().dimensions(), (30, 20));
+
+    let merger =
+        KnownSizeMerger::<Rgb<u8>, _>::new_from_raw_checked((10, 10), 0, 6, None, vec![0; needed]);
+    assert!(matches!(merger, Err(MergerError::InvalidLayout { .. })));
+}
+
+#[test]
+fn test_luma_merging() {
+    let tiles: Vec<BufferedImage<Luma<u8>>> = (0..6u32)
+        .map(|i| {
+            Image::from(ImageBuffer::from_fn(IMAGE_WIDTH, IMAGE_HEIGHT, |x, y| {
+                Luma([(x + y * 3 + i * 40) as u8])
+            }))
+        })
+        .collect();
+
+    let mut merger: KnownSizeMerger<Luma<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        3,
+        6,
+        Some(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        }),
+    );
+    merger.bulk_push(&tiles[..3].iter().collect::<Vec<_>>());
+    for tile in &tiles[3..] {
+        merger.push(tile);
+    }
+
+    let mut expected = ImageBuffer::<Luma<u8>, Vec<u8>>::new(
+        IMAGE_WIDTH * 3 + PADDING_X * 2,
+        IMAGE_HEIGHT * 2 + PADDING_Y,
+    );
+    for (i, tile) in tiles.iter().enumerate() {
+        let x = (i as u32 % 3) * (IMAGE_WIDTH + PADDING_X);
+        let y = (i as u32 / 3) * (IMAGE_HEIGHT + PADDING_Y);
+        overlay(&mut expected, &**tile, x as i64, y as i64);
+    }
+
+    assert_eq!(**merger.get_canvas(), expected);
+}
+
+#[test]
+fn test_canvas_dimensions_and_is_empty() {
+    fn describe<M: Merger<Rgba<u8>, Vec<u8>>>(merger: &M) -> ((u32, u32), bool) {
+        (merger.canvas_dimensions(), merger.is_empty())
+    }
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        Some(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        }),
+    );
+    let rows = TOTAL_IMAGES.div_ceil(IMAGES_PER_ROW);
+    let dimensions = (
+        IMAGE_WIDTH * IMAGES_PER_ROW + PADDING_X * (IMAGES_PER_ROW - 1),
+        IMAGE_HEIGHT * rows + PADDING_Y * (rows - 1),
+    );
+    assert_eq!(describe(&merger), (dimensions, true));
+
+    merger.push(&RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT));
+    assert_eq!(describe(&merger), (dimensions, false));
+}
+
+#[test]
+fn test_fill_empty_cells() {
+    let fill = Rgba([0, 0, 255, 255]);
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 7, None);
+
+    let cell_color = |merger: &KnownSizeMerger<Rgba<u8>, Vec<u8>>, index: u32| {
+        let x = (index % 3) * IMAGE_WIDTH + 1;
+        let y = (index / 3) * IMAGE_HEIGHT + 1;
+        *merger.get_canvas().get_pixel(x, y)
+    };
+
+    merger.bulk_push(&[&test_square; 5]);
+    merger.fill_empty_cells(fill);
+    for index in 0..9 {
+        let expected = match index {
+            0..5 => *test_square.get_pixel(1, 1),
+            _ => fill,
+        };
+        assert_eq!(cell_color(&merger, index), expected, "cell {index}");
+    }
+
+    merger.bulk_push(&[&test_square; 2]);
+    merger.fill_empty_cells(fill);
+    for index in 0..9 {
+        let expected = match index {
+            0..7 => *test_square.get_pixel(1, 1),
+            _ => fill,
+        };
+        assert_eq!(cell_color(&merger, index), expected, "cell {index}");
+    }
+}
+
+#[test]
+fn test_fill_empty_cells_overlapping() {
+    let fill = Rgba([0, 0, 255, 255]);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::with_placement((10, 10), (25, 25), 4, |index| Point {
+            x: index * 5,
+            y: index * 5,
+        });
+    merger.fill_empty_cells(fill);
+
+    let canvas = merger.get_canvas();
+    assert!((0..25).all(|i| *canvas.get_pixel(i, i) == fill));
+    assert_eq!(*canvas.get_pixel(24, 0), Rgba([0, 0, 0, 0]));
+}
+
+#[test]
+fn test_row_image() {
+    let tiles: Vec<RgbaImageBuffer> = (0..6u8)
+        .map(|i| RgbaImageBuffer::new_from_pixel(10, 8, Rgba([i * 40, 255 - i, 0, 255])))
+        .collect();
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((10, 8), 3, 6, None);
+    merger.bulk_push(&tiles.iter().collect::<Vec<_>>());
+
+    let mut strip: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((10, 8), 3, 3, None);
+    strip.bulk_push(&tiles[3..].iter().collect::<Vec<_>>());
+
+    assert_eq!(merger.row_image(1).as_ref(), Some(strip.get_canvas()));
+    assert!(merger.row_image(2).is_none());
+
+    // Padding between the images is kept, while the padding around the grid is left out.
+    let mut padded: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::builder()
+        .image_dimensions((10, 8))
+        .images_per_row(3)
+        .total_images(6)
+        .padding(Padding { x: 2, y: 2 })
+        .margin(Point { x: 5, y: 5 })
+        .build();
+    padded.bulk_push(&tiles.iter().collect::<Vec<_>>());
+    let row = padded.row_image(1).unwrap();
+    assert_eq!(row.dimensions(), (34, 8));
+    assert_eq!(*row.get_pixel(0, 0), Rgba([120, 252, 0, 255]));
+    assert_eq!(*row.get_pixel(10, 0), Rgba([0, 0, 0, 0]));
+    assert_eq!(*row.get_pixel(33, 7), Rgba([200, 250, 0, 255]));
+}
+
+#[test]
+fn test_push_vec_images_onto_slice_canvas() {
+    let mut buffer = vec![0u8; 4 * 2 * 2 * 4];
+    let mut merger: KnownSizeMerger<Rgba<u8>, &mut [u8]> =
+        KnownSizeMerger::new_from_raw((2, 2), 2, 4, None, buffer.as_mut_slice()).unwrap();
+
+    // The tiles are backed by a Vec while the canvas borrows its memory.
+    let red = BufferedImage::new_from_pixel(2, 2, Rgba([255, 0, 0, 255]));
+    let green = BufferedImage::new_from_pixel(2, 2, Rgba([0, 255, 0, 255]));
+    let blue = BufferedImage::new_from_pixel(2, 2, Rgba([0, 0, 255, 255]));
+    merger.push(&red);
+    merger.bulk_push(&[&green, &blue, &red]);
+    assert_eq!(merger.get_num_images(), 4);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+    assert_eq!(*canvas.get_pixel(3, 1), Rgba([0, 255, 0, 255]));
+    assert_eq!(*canvas.get_pixel(0, 2), Rgba([0, 0, 255, 255]));
+    assert_eq!(*canvas.get_pixel(3, 3), Rgba([255, 0, 0, 255]));
+}
+
+#[test]
+fn test_aggregate_max() {
+    let mut first: BufferedImage<Rgba<u8>> = BufferedImage::new(4, 3);
+    let mut second: BufferedImage<Rgba<u8>> = BufferedImage::new(4, 3);
+    for (x, y, pixel) in first.enumerate_pixels_mut() {
+        *pixel = Rgba([(x * 60) as u8, (y * 60) as u8, 100, 255]);
+    }
+    for (x, y, pixel) in second.enumerate_pixels_mut() {
+        *pixel = Rgba([(200 - x * 50) as u8, (y * 30) as u8, 50, 10]);
+    }
+
+    let mut merger: AggregateMerger<Rgba<u8>> = AggregateMerger::new((4, 3), Reduce::Max);
+    merger.push(&first);
+    merger.push(&second);
+    assert_eq!(merger.get_num_images(), 2);
+
+    for (x, y, pixel) in merger.get_canvas().enumerate_pixels() {
+        let expected = first
+            .get_pixel(x, y)
+            .map2(second.get_pixel(x, y), |a, b| a.max(b));
+        assert_eq!(*pixel, expected);
+    }
+}
+
+#[test]
+fn test_aggregate_min_and_mean() {
+    let dark = BufferedImage::new_from_pixel(2, 2, Rgb([10u8, 100, 255]));
+    let light = BufferedImage::new_from_pixel(2, 2, Rgb([20u8, 50, 0]));
+    let other = BufferedImage::new_from_pixel(2, 2, Rgb([0u8, 0, 0]));
+
+    let mut min: AggregateMerger<Rgb<u8>> = AggregateMerger::new((2, 2), Reduce::Min);
+    min.bulk_push(&[&dark, &light]);
+    assert_eq!(*min.get_canvas().get_pixel(1, 1), Rgb([10, 50, 0]));
+
+    let mut mean: AggregateMerger<Rgb<u8>> = AggregateMerger::new((2, 2), Reduce::Mean);
+    mean.bulk_push(&[&dark, &light]);
+    assert_eq!(*mean.get_canvas().get_pixel(0, 0), Rgb([15, 75, 128]));
+    mean.push(&other);
+    assert_eq!(*mean.get_canvas().get_pixel(0, 0), Rgb([10, 50, 85]));
+
+    assert!(matches!(
+        mean.try_push(&BufferedImage::<Rgb<u8>>::new(3, 2)),
+        Err(MergerError::DimensionMismatch {
+            index: None,
+            expected: (2, 2),
+            got: (3, 2)
+        })
+    ));
+    assert_eq!(mean.get_num_images(), 3);
+
+    // Containers longer than the image are cut down to the image.
+    let mut subpixels = [5u8, 6, 7].repeat(4);
+    subpixels.extend([0; 9]);
+    let oversized = BufferedImage::<Rgb<u8>>::new_from_raw(2, 2, subpixels).unwrap();
+    let mut max: AggregateMerger<Rgb<u8>> = AggregateMerger::new((2, 2), Reduce::Max);
+    max.push(&oversized);
+    max.push(&oversized);
+    assert_eq!(*max.get_canvas().get_pixel(1, 1), Rgb([5, 6, 7]));
+}
+
+#[test]
+fn test_trim_to_content() {
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 12, None);
+    let tile = BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgb([255, 0, 0]));
+    for _ in 0..5 {
+        merger.push(&tile);
+    }
+
+    let trimmed = merger.trim_to_content();
+    assert_eq!(trimmed.dimensions(), (IMAGE_WIDTH * 3, IMAGE_HEIGHT * 2));
+    assert_eq!(
+        *trimmed.get_pixel(IMAGE_WIDTH, IMAGE_HEIGHT),
+        Rgb([255, 0, 0])
+    );
+    assert_eq!(
+        *trimmed.get_pixel(IMAGE_WIDTH * 2, IMAGE_HEIGHT),
+        Rgb([0, 0, 0])
+    );
+
+    // With padding, the trimmed image starts at the first image and ends at the last one.
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        3,
+        12,
+        Some(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        }),
+    );
+    merger.bulk_push(&[&tile, &tile]);
+    assert_eq!(
+        merger.trim_to_content().dimensions(),
+        (IMAGE_WIDTH * 2 + PADDING_X, IMAGE_HEIGHT)
+    );
+
+    let empty: KnownSizeMerger<Rgb<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 12, None);
+    assert_eq!(empty.trim_to_content().dimensions(), (0, 0));
+}
+
+#[test]
+fn test_manifest() {
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        3,
+        12,
+        Some(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        }),
+    );
+    let tile = BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgb([255, 0, 0]));
+    for _ in 0..7 {
+        merger.push(&tile);
+    }
+
+    let manifest = merger.manifest();
+    assert_eq!(manifest.len(), merger.get_num_images() as usize);
+
+    let index = manifest[4].index;
+    assert_eq!(
+        manifest[4],
+        CellInfo {
+            index: 4,
+            x: (index % 3) * (IMAGE_WIDTH + PADDING_X),
+            y: (index / 3) * (IMAGE_HEIGHT + PADDING_Y),
+            width: IMAGE_WIDTH,
+            height: IMAGE_HEIGHT,
+        }
+    );
+}
+
+#[test]
+fn test_push_cropped() {
+    let mut bordered = BufferedImage::new_from_pixel(120, 120, Rgb([0u8, 0, 255]));
+    for (x, y, pixel) in bordered.enumerate_pixels_mut() {
+        if (10..110).contains(&x) && (10..110).contains(&y) {
+            *pixel = Rgb([x as u8, y as u8, 0]);
+        }
+    }
+    let cropped =
+        BufferedImage::from(image::imageops::crop_imm(&*bordered, 10, 10, 100, 100).to_image());
+
+    let mut expected: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((100, 100), 2, 4, None);
+    expected.push(&cropped);
+    expected.push(&cropped);
+
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((100, 100), 2, 4, None);
+    merger.push_cropped(&bordered, (10, 10, 100, 100)).unwrap();
+    merger.push_cropped(&bordered, (10, 10, 100, 100)).unwrap();
+    assert_eq!(merger.get_canvas(), expected.get_canvas());
+    assert_eq!(merger.get_num_images(), 2);
+
+    assert!(matches!(
+        merger.push_cropped(&bordered, (0, 0, 120, 120)),
+        Err(MergerError::DimensionMismatch {
+            index: None,
+            expected: (100, 100),
+            got: (120, 120)
+        })
+    ));
+    assert_eq!(merger.get_num_images(), 2);
+}
+
+#[test]
+fn test_push_very_large_tile() {
+    let mut tile: BufferedImage<Rgba<u8>> = BufferedImage::new(1024, 1024);
+    for (x, y, pixel) in tile.enumerate_pixels_mut() {
+        *pixel = Rgba([x as u8, y as u8, (x >> 8) as u8 ^ (y >> 8) as u8, 255]);
+    }
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (1024, 1024),
+        2,
+        2,
+        Some(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        }),
+    );
+    merger.push(&tile);
+    merger.push(&tile);
+
+    let mut expected = image::RgbaImage::new(1024 * 2 + PADDING_X, 1024);
+    overlay(&mut expected, &*tile, 0, 0);
+    overlay(&mut expected, &*tile, (1024 + PADDING_X) as i64, 0);
+    assert_eq!(**merger.get_canvas(), expected);
+}
+
+#[test]
+fn test_push_bgra() {
+    // The channels of the tile are stored blue, green, red, alpha.
+    let bgra = BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([30u8, 20, 10, 128]));
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 2, None);
+    merger.push(&bgra);
+    merger.push_bgra(&bgra);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(0, 0), Rgba([30, 20, 10, 128]));
+    assert!((IMAGE_WIDTH..IMAGE_WIDTH * 2)
+        .all(|x| (0..IMAGE_HEIGHT).all(|y| *canvas.get_pixel(x, y) == Rgba([10, 20, 30, 128]))));
+    assert_eq!(merger.get_num_images(), 2);
+}
+
+#[test]
+fn test_memory_footprint() {
+    let padding = Some(Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    });
+    let estimate = KnownSizeMerger::<Rgba<u16>, Vec<u16>>::estimate_footprint(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES - 3,
+        padding,
+    );
+    let merger: KnownSizeMerger<Rgba<u16>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES - 3,
+        padding,
+    );
+
+    let (width, height) = merger.canvas_dimensions();
+    assert_eq!(merger.memory_footprint(), (width * height * 4 * 2) as usize);
+    assert_eq!(estimate, merger.memory_footprint());
+
+    assert_eq!(
+        KnownSizeMerger::<Rgba<u16>, Vec<u16>>::estimate_footprint((0, 10), 1, 1, None),
+        0
+    );
+}
+
+#[test]
+fn test_push_blended() {
+    let mut merger = KnownSizeMerger::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(2)
+        .total_images(2)
+        .background(Rgba([0u8, 255, 0, 255]))
+        .build();
+    let red = BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255u8, 0, 0, 128]));
+    merger.push_blended(&red, ColorSpace::Srgb);
+    merger.push_blended(&red, ColorSpace::Linear);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(0, 0), Rgba([128, 127, 0, 255]));
+    assert_eq!(*canvas.get_pixel(IMAGE_WIDTH, 0), Rgba([188, 187, 0, 255]));
+}
+
+#[test]
+fn test_canvas_pixel() {
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    merger.push(&BufferedImage::new(IMAGE_WIDTH, IMAGE_HEIGHT));
+    merger.push(&BufferedImage::new_from_pixel(
+        IMAGE_WIDTH,
+        IMAGE_HEIGHT,
+        Rgb([1, 2, 3]),
+    ));
+
+    assert_eq!(
+        merger.canvas_pixel(IMAGE_WIDTH + 5, IMAGE_HEIGHT - 1),
+        Some(Rgb([1, 2, 3]))
+    );
+    assert_eq!(merger.canvas_pixel(5, 5), Some(Rgb([0, 0, 0])));
+    assert_eq!(merger.canvas_pixel(IMAGE_WIDTH * 2, 0), None);
+    assert_eq!(merger.canvas_pixel(0, IMAGE_HEIGHT * 2), None);
+}
+
+/// Pushes a 40x40 white tile into a 100x100 black cell with the given anchor and returns the offset of the tile.
+fn anchored_tile_offset(anchor: Anchor) -> (u32, u32) {
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((100, 100), 1, 1, None);
+    let tile = BufferedImage::new_from_pixel(40, 40, Rgb([255u8; 3]));
+    merger.push_anchored(&tile, anchor, Rgb([0, 0, 0])).unwrap();
+
+    let canvas = merger.get_canvas();
+    let (x, y, _) = canvas
+        .enumerate_pixels()
+        .find(|(_, _, pixel)| **pixel == Rgb([255; 3]))
+        .unwrap();
+    assert_eq!(
+        canvas
+            .pixels()
+            .filter(|pixel| **pixel == Rgb([255; 3]))
+            .count(),
+        40 * 40
+    );
+
+    (x, y)
+}
+
+#[test]
+fn test_push_anchored() {
+    assert_eq!(anchored_tile_offset(Anchor::TopRight), (60, 0));
+    assert_eq!(anchored_tile_offset(Anchor::BottomLeft), (0, 60));
+    assert_eq!(anchored_tile_offset(Anchor::TopLeft), (0, 0));
+    assert_eq!(anchored_tile_offset(Anchor::BottomRight), (60, 60));
+    assert_eq!(anchored_tile_offset(Anchor::Center), (30, 30));
+
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((100, 100), 1, 1, None);
+    assert!(matches!(
+        merger.push_anchored(&BufferedImage::new(101, 40), Anchor::TopLeft, Rgb([0; 3])),
+        Err(MergerError::DimensionMismatch {
+            index: None,
+            expected: (100, 100),
+            got: (101, 40)
+        })
+    ));
+    assert!(merger.is_empty());
+}
+
+#[test]
+fn test_write_png() {
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 5, None);
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    merger.bulk_push(&[&test_square, &test_square, &test_square, &test_square]);
+
+    let mut png = Vec::new();
+    merger.write_png(&mut png).unwrap();
+
+    let decoded = image::load_from_memory_with_format(&png, image::ImageFormat::Png)
+        .unwrap()
+        .into_rgba8();
+    assert_eq!(&decoded, &**merger.get_canvas());
+
+    // A container longer than the canvas only has the canvas encoded.
+    let (width, height) = merger.get_canvas().dimensions();
+    let mut oversized: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new_from_raw(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        3,
+        5,
+        None,
+        vec![0u8; (width * height * 4) as usize + 64],
+    )
+    .unwrap();
+    oversized.bulk_push(&[&test_square, &test_square, &test_square, &test_square]);
+
+    let mut png = Vec::new();
+    oversized.write_png(&mut png).unwrap();
+    let decoded = image::load_from_memory_with_format(&png, image::ImageFormat::Png)
+        .unwrap()
+        .into_rgba8();
+    assert!(decoded.pixels().eq(merger.get_canvas().pixels()));
+}
+
+#[test]
+fn test_bulk_push_blended_skips_transparent() {
+    let background = Rgba([0u8, 0, 255, 255]);
+    let mut merger = KnownSizeMerger::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(3)
+        .total_images(6)
+        .background(background)
+        .build();
+
+    let transparent =
+        BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255u8, 0, 0, 0]));
+    let opaque = BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255u8, 0, 0, 255]));
+    merger.bulk_push_blended(
+        &[&transparent, &opaque, &transparent, &opaque],
+        ColorSpace::Srgb,
+    );
+    assert_eq!(merger.get_num_images(), 4);
+
+    let expected = [
+        background,
+        Rgba([255, 0, 0, 255]),
+        background,
+        Rgba([255, 0, 0, 255]),
+    ];
+    for (index, expected) in expected.into_iter().enumerate() {
+        let x = (index as u32 % 3) * IMAGE_WIDTH;
+        let y = (index as u32 / 3) * IMAGE_HEIGHT;
+        assert!((0..IMAGE_HEIGHT).all(|dy| (0..IMAGE_WIDTH)
+            .all(|dx| *merger.get_canvas().get_pixel(x + dx, y + dy) == expected)));
+    }
+
+    // The next push lands after the skipped cells.
+    merger.push_blended(&opaque, ColorSpace::Srgb);
+    assert_eq!(
+        *merger.get_canvas().get_pixel(IMAGE_WIDTH, IMAGE_HEIGHT),
+        Rgba([255, 0, 0, 255])
+    );
+}
+
+/// Merges 5 distinct tiles onto a 3 per row canvas with padding, then checks the canvas against `overlay`, including after
+/// removing one of the tiles.
+fn check_wide_subpixel_merge<P>(tile: impl Fn(u32, u32, u32) -> P)
+where
+    P: Pixel + Sync + Send + 'static,
+    <P as Pixel>::Subpixel: Sync + Send + PartialEq + std::fmt::Debug,
+{
+    let padding = Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    };
+    let tiles: Vec<BufferedImage<P>> = (0..5)
+        .map(|i| {
+            Image::from(image::ImageBuffer::from_fn(
+                IMAGE_WIDTH,
+                IMAGE_HEIGHT,
+                |x, y| tile(i, x, y),
+            ))
+        })
+        .collect();
+
+    let mut merger: KnownSizeMerger<P, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 6, Some(padding));
+    merger.push(&tiles[0]);
+    merger.bulk_push(&tiles[1..].iter().collect::<Vec<_>>());
+
+    let (width, height) = merger.canvas_dimensions();
+    let mut expected: image::ImageBuffer<P, Vec<P::Subpixel>> =
+        image::ImageBuffer::new(width, height);
+    for (i, tile) in tiles.iter().enumerate() {
+        let x = (i as u32 % 3) * (IMAGE_WIDTH + PADDING_X);
+        let y = (i as u32 / 3) * (IMAGE_HEIGHT + PADDING_Y);
+        overlay(&mut expected, &**tile, x as i64, y as i64);
+    }
+    assert_eq!(merger.as_subpixels(), expected.as_raw().as_slice());
+
+    // Removed tiles are zeroed with the full width of each subpixel. The blank tile is transparent, so `replace` is used
+    // rather than `overlay`, which would blend it away.
+    merger.remove_image(4).unwrap();
+    let blank: image::ImageBuffer<P, Vec<P::Subpixel>> =
+        image::ImageBuffer::new(IMAGE_WIDTH, IMAGE_HEIGHT);
+    image::imageops::replace(
+        &mut expected,
+        &blank,
+        (IMAGE_WIDTH + PADDING_X) as i64,
+        (IMAGE_HEIGHT + PADDING_Y) as i64,
+    );
+    assert_eq!(merger.as_subpixels(), expected.as_raw().as_slice());
+}
+
+#[test]
+fn test_u16_merge() {
+    check_wide_subpixel_merge(|i, x, y| {
+        Rgba([
+            (x * 600 + i) as u16,
+            (y * 600) as u16,
+            (i * 10_000) as u16,
+            u16::MAX,
+        ])
+    });
+}
+
+#[test]
+fn test_f32_merge() {
+    check_wide_subpixel_merge(|i, x, y| {
+        Rgb([
+            x as f32 / IMAGE_WIDTH as f32,
+            y as f32 / IMAGE_HEIGHT as f32,
+            i as f32 * 0.2 + 0.1,
+        ])
+    });
+}
+
+#[test]
+fn test_plan() {
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        3,
+        8,
+        Some(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        }),
+    );
+    let tile = BufferedImage::new(IMAGE_WIDTH, IMAGE_HEIGHT);
+    merger.push(&tile);
+
+    let canvas_before = merger.as_subpixels().to_vec();
+    let plan = merger.plan(5);
+    assert_eq!(merger.get_num_images(), 1);
+    assert_eq!(merger.as_subpixels(), canvas_before.as_slice());
+
+    let indices: Vec<u32> = (0..5).map(|_| merger.push_indexed(&tile)).collect();
+    let pasted: Vec<Point> = merger
+        .cell_rects()
+        .filter(|(index, _, _)| indices.contains(index))
+        .map(|(_, loc, _)| loc)
+        .collect();
+    assert_eq!(plan, pasted);
+
+    // Only as many pushes as fit on the canvas are planned.
+    assert_eq!(merger.plan(5).len(), 2);
+}
+
+#[test]
+fn test_push_repeated() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 8, 60, None);
+    expected.push(&test_square);
+    expected.bulk_push(&vec![&test_square; 50]);
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 8, 60, None);
+    merger.push(&test_square);
+    merger.push_repeated(&test_square, 50).unwrap();
+    assert_eq!(merger.get_canvas(), expected.get_canvas());
+    assert_eq!(merger.get_num_images(), 51);
+
+    assert!(matches!(
+        merger.push_repeated(&test_square, 10),
+        Err(MergerError::IndexOutOfBounds {
+            index: 60,
+            capacity: 60
+        })
+    ));
+    assert!(matches!(
+        merger.push_repeated(&RgbaImageBuffer::new(1, 1), 1),
+        Err(MergerError::DimensionMismatch { index: None, .. })
+    ));
+    assert_eq!(merger.get_num_images(), 51);
+
+    merger.push_repeated(&test_square, 9).unwrap();
+    assert_eq!(merger.get_num_images(), 60);
+}
+
+#[test]
+fn test_clear_rect() {
+    let tile = BufferedImage::new_from_pixel(100, 100, Rgb([200, 100, 50]));
+    let blank = Rgb([0, 0, 255]);
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((100, 100), 2, 4, None);
+    merger.bulk_push(&[&tile; 4]);
+
+    // Spans the right half of the first cell and the left half of the second.
+    merger.clear_rect(50, 25, 150, 50, blank);
+    for (x, y) in [(50, 25), (99, 50), (100, 50), (199, 74)] {
+        assert_eq!(merger.canvas_pixel(x, y), Some(blank));
+    }
+    for (x, y) in [(49, 25), (50, 24), (50, 75), (199, 75), (0, 0), (150, 150)] {
+        assert_eq!(merger.canvas_pixel(x, y), Some(Rgb([200, 100, 50])));
+    }
+    assert_eq!(merger.get_num_images(), 4);
+
+    // Rectangles are clipped to the canvas.
+    merger.clear_rect(190, 190, 50, 50, blank);
+    assert_eq!(merger.canvas_pixel(199, 199), Some(blank));
+    assert_eq!(merger.canvas_pixel(189, 199), Some(Rgb([200, 100, 50])));
+    merger.clear_rect(300, 0, 10, 10, blank);
+}
+
+#[test]
+fn test_from_rows() {
+    let tiles: Vec<BufferedImage<Rgb<u8>>> = (0..16u8)
+        .map(|index| {
+            Image::from(image::ImageBuffer::from_fn(10, 8, |x, y| {
+                Rgb([index * 15, x as u8 * 20, y as u8 * 30])
+            }))
+        })
+        .collect();
+    let tiles_ref: Vec<&BufferedImage<Rgb<u8>>> = tiles.iter().collect();
+    let padding = Some(Point { x: 3, y: 5 });
+
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((10, 8), 4, 16, padding);
+    merger.bulk_push(&tiles_ref);
+    let rows: Vec<BufferedImage<Rgb<u8>>> =
+        (0..4).map(|row| merger.row_image(row).unwrap()).collect();
+
+    let rebuilt = KnownSizeMerger::from_rows(rows, 4, padding).unwrap();
+    assert_eq!(rebuilt.get_canvas(), merger.get_canvas());
+    assert_eq!(rebuilt.get_num_images(), 16);
+    assert_eq!(rebuilt.remaining_capacity(), 0);
+
+    let strip = merger.row_image(0).unwrap();
+    let short = BufferedImage::new(strip.width() - 1, strip.height());
+    assert!(KnownSizeMerger::from_rows(vec![strip, short], 4, padding).is_none());
+    let strip = merger.row_image(0).unwrap();
+    assert!(KnownSizeMerger::from_rows(vec![strip], 3, padding).is_none());
+    assert!(KnownSizeMerger::<Rgb<u8>, _>::from_rows(Vec::new(), 4, padding).is_none());
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn test_test_images() {
+    let gradient = RgbaImageBuffer::test_gradient(300, 200);
+    assert_eq!(gradient.dimensions(), (300, 200));
+    assert_eq!(*gradient.get_pixel(0, 0), Rgba([0, 0, 0, 255]));
+    assert_eq!(*gradient.get_pixel(299, 0), Rgba([43, 0, 43, 255]));
+    assert_eq!(*gradient.get_pixel(0, 199), Rgba([0, 199, 199, 255]));
+    assert_eq!(*gradient.get_pixel(299, 199), Rgba([43, 199, 242, 255]));
+    assert_eq!(
+        RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT),
+        RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT)
+    );
+
+    let (a, b) = (Rgb([255, 0, 0]), Rgb([0, 0, 255]));
+    let checkerboard = BufferedImage::test_checkerboard(25, 10, 4, a, b);
+    assert_eq!(*checkerboard.get_pixel(0, 0), a);
+    assert_eq!(*checkerboard.get_pixel(3, 3), a);
+    assert_eq!(*checkerboard.get_pixel(4, 0), b);
+    assert_eq!(*checkerboard.get_pixel(0, 4), b);
+    assert_eq!(*checkerboard.get_pixel(4, 4), a);
+    assert_eq!(*checkerboard.get_pixel(24, 9), a);
+}
+
+#[test]
+fn test_bulk_push_collecting() {
+    let tile: BufferedImage<Rgb<u8>> = BufferedImage::new_from_pixel(10, 10, Rgb([200, 100, 50]));
+    let wide: BufferedImage<Rgb<u8>> = BufferedImage::new(12, 10);
+    let tall: BufferedImage<Rgb<u8>> = BufferedImage::new(10, 11);
+    let mut images = vec![&tile; 10];
+    images[3] = &wide;
+    images[8] = &tall;
+
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((10, 10), 4, 12, None);
+    let errors = merger.bulk_push_collecting(&images);
+
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[0],
+        (
+            3,
+            MergerError::DimensionMismatch {
+                index: Some(3),
+                got: (12, 10),
+                ..
+            }
+        )
+    ));
+    assert!(matches!(
+        errors[1],
+        (
+            8,
+            MergerError::DimensionMismatch {
+                index: Some(8),
+                got: (10, 11),
+                ..
+            }
+        )
+    ));
+
+    assert_eq!(merger.get_num_images(), 8);
+    for index in 0..10 {
+        let (x, y) = ((index % 4) * 10 + 5, (index / 4) * 10 + 5);
+        let expected = match index {
+            3 | 8 => Rgb([0, 0, 0]),
+            _ => Rgb([200, 100, 50]),
+        };
+        assert_eq!(merger.canvas_pixel(x, y), Some(expected), "cell {index}");
+    }
+
+    // Later pushes continue after the skipped cells.
+    merger.push(&tile);
+    assert_eq!(merger.canvas_pixel(25, 25), Some(Rgb([200, 100, 50])));
+    assert!(merger.bulk_push_collecting(&[&tile]).is_empty());
+    assert_eq!(merger.get_num_images(), 10);
+}
+
+#[test]
+fn test_zero_padding() {
+    // 3 images per row with 5 images leaves an unused slot at the end of the second row.
+    let padding = Point { x: 5, y: 3 };
+    let (width, height) = (3 * 10 + 2 * 5, 2 * 10 + 3);
+    let mut container = vec![0xABu8; width * height * 3 + 127];
+    let mut merger: KnownSizeMerger<Rgb<u8>, &mut [u8]> =
+        KnownSizeMerger::new_from_raw((10, 10), 3, 5, Some(padding), container.as_mut_slice())
+            .unwrap();
+    let tile = BufferedImage::new_from_pixel(10, 10, Rgb([1, 2, 3]));
+    merger.bulk_push(&[&tile; 4]);
+    merger.zero_padding();
+
+    let blank = Rgb([0; 3]);
+    for (x, y) in [(10, 0), (14, 9), (0, 10), (39, 12), (30, 13), (39, 22)] {
+        assert_eq!(merger.canvas_pixel(x, y), Some(blank), "gutter ({x}, {y})");
+    }
+    for (x, y) in [(0, 0), (9, 9), (15, 0), (34, 9), (0, 13), (9, 22)] {
+        assert_eq!(
+            merger.canvas_pixel(x, y),
+            Some(Rgb([1, 2, 3])),
+            "image ({x}, {y})"
+        );
+    }
+    // The fifth cell has no image yet, so it is zeroed along with the gutters.
+    assert_eq!(merger.canvas_pixel(15, 13), Some(blank));
+    assert_eq!(merger.canvas_pixel(24, 22), Some(blank));
+
+    let zeroed = merger
+        .as_subpixels()
+        .iter()
+        .filter(|&&value| value == 0)
+        .count();
+    assert_eq!(zeroed, (width * height - 4 * 100) * 3);
+
+    // The tail of the container past the canvas is left untouched.
+    drop(merger);
+    assert_eq!(&container[width * height * 3..], &[0xAB; 127]);
+}
+
+#[test]
+fn test_fill_with() {
+    let color = |index: u32| Rgb([index as u8 * 10, 255 - index as u8, 7]);
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> =
+        KnownSizeMerger::new((10, 10), 4, 14, Some(Point { x: 2, y: 2 }));
+    merger.push(&BufferedImage::new_from_pixel(10, 10, Rgb([1, 1, 1])));
+    merger.fill_with(|index| BufferedImage::new_from_pixel(10, 10, color(index)));
+
+    assert_eq!(merger.get_num_images(), 14);
+    assert_eq!(merger.remaining_capacity(), 0);
+    assert_eq!(merger.canvas_pixel(0, 0), Some(Rgb([1, 1, 1])));
+    for index in 1..14 {
+        let (x, y) = ((index % 4) * 12 + 9, (index / 4) * 12 + 9);
+        assert_eq!(
+            merger.canvas_pixel(x, y),
+            Some(color(index)),
+            "cell {index}"
+        );
+    }
+}
+
+#[test]
+fn test_bulk_push_dimension_mismatch() {
+    let tile: BufferedImage<Rgb<u8>> = BufferedImage::new_from_pixel(10, 10, Rgb([200, 100, 50]));
+    let wrong: BufferedImage<Rgb<u8>> = BufferedImage::new_from_pixel(10, 9, Rgb([200, 100, 50]));
+    let images = vec![&tile, &tile, &wrong, &tile];
+
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((10, 10), 2, 4, None);
+    let before = merger.as_subpixels().to_vec();
+
+    assert!(matches!(
+        merger.try_bulk_push(&images),
+        Err(MergerError::DimensionMismatch {
+            index: Some(2),
+            expected: (10, 10),
+            got: (10, 9),
+        })
+    ));
+    assert_eq!(merger.as_subpixels(), before.as_slice());
+    assert_eq!(merger.get_num_images(), 0);
+
+    merger.try_bulk_push(&[&tile, &tile, &tile]).unwrap();
+    assert_eq!(merger.get_num_images(), 3);
+    assert_eq!(*merger.get_canvas().get_pixel(5, 15), Rgb([200, 100, 50]));
+}
+
+#[test]
+#[should_panic(expected = "Every image must match the image dimensions of the merger!")]
+fn test_bulk_push_dimension_mismatch_panics() {
+    let tile: BufferedImage<Rgb<u8>> = BufferedImage::new(10, 10);
+    let wrong: BufferedImage<Rgb<u8>> = BufferedImage::new(11, 10);
+
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((10, 10), 2, 4, None);
+    merger.bulk_push(&[&tile, &wrong]);
+}
+
+#[test]
+fn test_push_dimension_checks() {
+    let wrong: BufferedImage<Rgba<u8>> = BufferedImage::new(11, 10);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((10, 10), 2, 4, None);
+
+    assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(
+        || merger.push_blended(&wrong, ColorSpace::Srgb)
+    ))
+    .is_err());
+    assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(
+        || merger.bulk_push_blended(&[&wrong], ColorSpace::Srgb)
+    ))
+    .is_err());
+    assert!(
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| merger.push_bgra(&wrong)))
+            .is_err()
+    );
+    assert!(
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| merger.push(&wrong))).is_err()
+    );
+    assert!(
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| merger.push_indexed(&wrong)))
+            .is_err()
+    );
+    assert_eq!(merger.get_num_images(), 0);
+    assert!(merger.get_canvas().pixels().all(|p| p[3] == 0));
+}
+
+#[test]
+fn test_bulk_push_unchecked() {
+    let tile = BufferedImage::new_from_pixel(10, 10, Rgb([1u8, 2, 3]));
+
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((10, 10), 2, 4, None);
+    // SAFETY: Every tile matches the image dimensions of the merger.
+    unsafe { merger.bulk_push_unchecked(&[&tile; 3]) };
+
+    let mut expected: KnownSizeMerger<Rgb<u8>, _> = KnownSizeMerger::new((10, 10), 2, 4, None);
+    expected.bulk_push(&[&tile; 3]);
+    assert_eq!(merger, expected);
+}
+
+fn fixture_path(name: &str) -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+fn load_fixture(name: &str) -> RgbaImageBuffer {
+    let buffer = std::fs::read(fixture_path(name)).unwrap();
+    RgbaImageBuffer::from_with_format(buffer, image::ImageFormat::Png)
+}
+
+#[test]
+fn test_bulk_push_paths() {
+    let paths = [
+        fixture_path("gradient.png"),
+        fixture_path("solid.png"),
+        fixture_path("gradient.png"),
+    ];
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((16, 16), 2, 4, None);
+    merger.bulk_push_paths(&paths).unwrap();
+    assert_eq!(merger.get_num_images(), 3);
+
+    let gradient = load_fixture("gradient.png");
+    let solid = load_fixture("solid.png");
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((16, 16), 2, 4, None);
+    expected.bulk_push(&[&gradient, &solid, &gradient]);
+
+    assert_eq!(merger.get_canvas(), expected.get_canvas());
+}
+
+#[test]
+fn test_bulk_push_paths_missing_file() {
+    let paths = [fixture_path("gradient.png"), fixture_path("missing.png")];
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((16, 16), 2, 4, None);
+    let result = merger.bulk_push_paths(&paths);
+
+    assert!(matches!(result, Err(MergerError::Decode { path, .. }) if path == paths[1]));
+    assert_eq!(merger.get_num_images(), 0);
+}
+
+#[test]
+fn test_bulk_push_paths_dimension_mismatch() {
+    let paths = [fixture_path("gradient.png"), fixture_path("small.png")];
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((16, 16), 2, 4, None);
+    let result = merger.bulk_push_paths(&paths);
+
+    assert!(matches!(
+        result,
+        Err(MergerError::DimensionMismatch {
+            index: Some(1),
+            expected: (16, 16),
+            got: (8, 8)
+        })
+    ));
+    assert_eq!(merger.get_num_images(), 0);
+    assert!(merger.get_canvas().pixels().all(|p| p[3] == 0));
+}
+
+#[test]
+fn test_merger_error_as_dyn_error() {
+    let paths = [fixture_path("missing.png")];
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((16, 16), 2, 4, None);
+    let error: Box<dyn std::error::Error> = merger.bulk_push_paths(&paths).unwrap_err().into();
+
+    assert_eq!(
+        error.to_string(),
+        format!("could not decode the image at {}", paths[0].display())
+    );
+    let source = error.source().unwrap();
+    assert!(source.downcast_ref::<image::ImageError>().is_some());
+
+    let error = MergerError::DimensionMismatch {
+        index: Some(2),
+        expected: (10, 10),
+        got: (10, 9),
+    };
+    assert_eq!(
+        error.to_string(),
+        "expected an image of 10x10 pixels, but got 10x9 at index 2"
+    );
+    assert!(std::error::Error::source(&error).is_none());
+}
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_merge_paths_blocking() {
+    let paths = vec![
+        fixture_path("solid.png"),
+        fixture_path("gradient.png"),
+        fixture_path("solid.png"),
+    ];
+
+    let merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((16, 16), 2, 4, None);
+    let canvas = merger.merge_paths_blocking(paths).await.unwrap().unwrap();
+
+    let gradient = load_fixture("gradient.png");
+    let solid = load_fixture("solid.png");
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((16, 16), 2, 4, None);
+    expected.bulk_push(&[&solid, &gradient, &solid]);
+    assert_eq!(&canvas, expected.get_canvas());
+
+    let merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((16, 16), 2, 4, None);
+    let result = merger
+        .merge_paths_blocking(vec![fixture_path("missing.png")])
+        .await
+        .unwrap();
+    assert!(matches!(result, Err(MergerError::Decode { .. })));
+}
+
+#[test]
+fn test_bulk_push_encoded() {
+    let gradient = std::fs::read(fixture_path("gradient.png")).unwrap();
+    let solid = std::fs::read(fixture_path("solid.png")).unwrap();
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((16, 16), 2, 4, None);
+    merger
+        .bulk_push_encoded(&[&gradient, &solid, &gradient], image::ImageFormat::Png)
+        .unwrap();
+
+    let gradient_image = load_fixture("gradient.png");
+    let solid_image = load_fixture("solid.png");
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((16, 16), 2, 4, None);
+    expected.bulk_push(&[&gradient_image, &solid_image, &gradient_image]);
+    assert_eq!(merger, expected);
+
+    let garbage = [0xde, 0xad, 0xbe, 0xef];
+    let result = merger.bulk_push_encoded(&[&solid, &garbage], image::ImageFormat::Png);
+    assert!(matches!(
+        result,
+        Err(MergerError::DecodeBuffer { index: 1, .. })
+    ));
+    assert_eq!(merger.get_num_images(), 3);
+
+    let mut small = Vec::new();
+    RgbaImageBuffer::new_from_pixel(3, 3, Rgba([1, 2, 3, 255]))
+        .write_to(
+            &mut std::io::Cursor::new(&mut small),
+            image::ImageFormat::Png,
+        )
+        .unwrap();
+    let result = merger.bulk_push_encoded(&[&solid, &small], image::ImageFormat::Png);
+    assert!(matches!(
+        result,
+        Err(MergerError::DimensionMismatch {
+            index: Some(1),
+            expected: (16, 16),
+            got: (3, 3)
+        })
+    ));
+    assert_eq!(merger, expected);
+}
+
+#[test]
+fn test_try_from_with_format_garbage() {
+    let garbage = vec![0xde, 0xad, 0xbe, 0xef, 0x00, 0x01, 0x02, 0x03];
+    let image = RgbaImageBuffer::try_from_with_format(garbage, image::ImageFormat::Png);
+    assert!(image.is_err());
+
+    let buffer = std::fs::read(fixture_path("solid.png")).unwrap();
+    let image = RgbaImageBuffer::try_from_with_format(buffer, image::ImageFormat::Png).unwrap();
+    assert_eq!(*image.get_pixel(0, 0), Rgba([0, 128, 255, 255]));
+}
+
+#[test]
+fn test_from_reader() {
+    let buffer = std::fs::read(fixture_path("gradient.png")).unwrap();
+    let expected = RgbaImageBuffer::from_with_format(buffer.clone(), image::ImageFormat::Png);
+
+    let image = RgbaImageBuffer::from_reader(std::io::Cursor::new(buffer), image::ImageFormat::Png)
+        .unwrap();
+    assert_eq!(image, expected);
+
+    let garbage = std::io::Cursor::new(vec![0xde, 0xad, 0xbe, 0xef]);
+    assert!(RgbaImageBuffer::from_reader(garbage, image::ImageFormat::Png).is_err());
+}
+
+#[test]
+fn test_encode_to_vec() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    merger.bulk_push(&[&test_square; 3]);
+
+    let encoded = merger.encode_to_vec(image::ImageFormat::Png).unwrap();
+    let decoded = RgbaImageBuffer::from_with_format(encoded, image::ImageFormat::Png);
+    assert_eq!(&decoded, merger.get_canvas());
+}
+
+#[test]
+fn test_encode_jpeg() {
+    let test_square = BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgb([255, 0, 0]));
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 2, None);
+    merger.bulk_push(&[&test_square; 2]);
+
+    let encoded = merger.encode_jpeg(90).unwrap();
+    assert_eq!(&encoded[..2], &[0xFF, 0xD8]);
+
+    let decoded = BufferedImage::<Rgb<u8>>::from_with_format(encoded, image::ImageFormat::Jpeg);
+    assert_eq!(decoded.dimensions(), merger.get_canvas().dimensions());
+}
+
+#[test]
+fn test_from_with_format_luma_f32() {
+    let tile: BufferedImage<Luma<u16>> = BufferedImage::new_from_pixel(10, 10, Luma([32768]));
+    let mut merger: KnownSizeMerger<Luma<u16>, _> = KnownSizeMerger::new((10, 10), 2, 4, None);
+    merger.bulk_push(&[&tile; 3]);
+    let encoded = merger.encode_to_vec(image::ImageFormat::Png).unwrap();
+
+    let decoded =
+        BufferedImage::<Luma<f32>>::from_with_format(encoded.clone(), image::ImageFormat::Png);
+    assert_eq!(decoded.dimensions(), (20, 20));
+    assert_eq!(*decoded.get_pixel(15, 5), Luma([32768.0 / 65535.0]));
+    assert_eq!(*decoded.get_pixel(15, 15), Luma([0.0]));
+
+    let decoded = BufferedImage::<LumaA<f32>>::from_with_format(encoded, image::ImageFormat::Png);
+    assert_eq!(*decoded.get_pixel(5, 15), LumaA([32768.0 / 65535.0, 1.0]));
+}
+
+#[test]
+fn test_push_scaled() {
+    let tile: BufferedImage<Rgb<u8>> = Image::from(image::ImageBuffer::from_fn(3, 3, |x, y| {
+        Rgb([x as u8 * 100, y as u8 * 100, 7])
+    }));
+    let mut merger: KnownSizeMerger<Rgb<u8>, _> =
+        KnownSizeMerger::new((6, 6), 2, 2, Some(Point { x: 1, y: 0 }));
+    merger.push(&BufferedImage::new(6, 6));
+    merger.push_scaled(&tile, 2).unwrap();
+
+    for y in 0..6 {
+        for x in 0..6 {
+            assert_eq!(
+                merger.canvas_pixel(7 + x, y),
+                Some(*tile.get_pixel(x / 2, y / 2))
+            );
+        }
+    }
+    assert!(matches!(
+        merger.push_scaled(&tile, 3),
+        Err(MergerError::DimensionMismatch {
+            index: None,
+            expected: (6, 6),
+            got: (9, 9),
+        })
+    ));
+    assert_eq!(merger.get_num_images(), 2);
+}
+
+#[test]
+fn test_into_dynamic() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    merger.bulk_push(&[&test_square; 3]);
+
+    let copied = merger.to_dynamic();
+    let image::DynamicImage::ImageRgba8(buffer) = &copied else {
+        panic!("Expected an ImageRgba8, got {:?}", copied.color());
+    };
+    assert_eq!(buffer.as_raw().as_slice(), merger.as_subpixels());
+
+    let canvas = merger.get_canvas().as_raw().clone();
+    let moved = merger.into_dynamic();
+    assert!(
+        matches!(&moved, image::DynamicImage::ImageRgba8(buffer) if *buffer.as_raw() == canvas)
+    );
+
+    let luma: KnownSizeMerger<Luma<u16>, _> = KnownSizeMerger::new((4, 4), 2, 2, None);
+    assert!(matches!(
+        luma.to_dynamic(),
+        image::DynamicImage::ImageLuma16(_)
+    ));
+}
+
+#[test]
+fn test_push_converted() {
+    let rgb_square: BufferedImage<Rgb<u8>> =
+        BufferedImage::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgb([10, 20, 30]));
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 2, None);
+    merger.push_converted(&rgb_square);
+
+    assert_eq!(merger.get_num_images(), 1);
+    assert_eq!(
+        *merger.get_canvas().get_pixel(5, 5),
+        Rgba([10, 20, 30, 255])
+    );
+    assert_eq!(
+        *merger.get_canvas().get_pixel(IMAGE_WIDTH + 5, 5),
+        Rgba([0, 0, 0, 0])
+    );
+}
+
+#[test]
+fn test_bulk_push_with_progress() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        None,
+    );
+
+    let reported = std::sync::Mutex::new(std::collections::HashSet::new());
+    merger.bulk_push_with_progress(&vec![&test_square; TOTAL_IMAGES as usize], |completed| {
+        reported.lock().unwrap().insert(completed);
+    });
+
+    let reported = reported.into_inner().unwrap();
+    let expected: std::collections::HashSet<usize> = (1..=TOTAL_IMAGES as usize).collect();
+    assert_eq!(reported, expected);
+    assert_eq!(
+        merger.get_canvas(),
+        &merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES, 0, 0)
+    );
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_thread_pool() {
+    let thread_pool = std::sync::Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap(),
+    );
+
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        IMAGES_PER_ROW,
+        TOTAL_IMAGES,
+        None,
+    )
+    .with_thread_pool(thread_pool);
+
+    merger.bulk_push_with_progress(&vec![&test_square; TOTAL_IMAGES as usize], |_| {
+        assert_eq!(rayon::current_num_threads(), 2);
+    });
+
+    assert_eq!(
+        merger.get_canvas(),
+        &merge_images_slow(IMAGES_PER_ROW, TOTAL_IMAGES, 0, 0)
+    );
+}
+
+#[test]
+fn test_sequential_paste_matches_parallel() {
+    for size in [1, 4] {
+        let mut tile = RgbaImageBuffer::new(size, size);
+        for (x, y, pixel) in tile.enumerate_pixels_mut() {
+            *pixel = Rgba([x as u8 * 50, y as u8 * 50, 255, 255]);
+        }
+
+        let mut sequential: KnownSizeMerger<Rgba<u8>, _> =
+            KnownSizeMerger::new((size, size), 3, 9, Some(Padding { x: 1, y: 1 }));
+        let mut parallel: KnownSizeMerger<Rgba<u8>, _> =
+            KnownSizeMerger::new((size, size), 3, 9, Some(Padding { x: 1, y: 1 }))
+                .with_sequential_paste_threshold(0);
+
+        for _ in 0..9 {
+            sequential.push(&tile);
+            parallel.push(&tile);
+        }
+
+        assert_eq!(sequential.get_canvas(), parallel.get_canvas());
+    }
+}
+
+#[test]
+fn test_edge_padding() {
+    let edges = EdgePadding {
+        top: 20,
+        right: 0,
+        bottom: 0,
+        left: 5,
+    };
+
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new_with_gutters((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, edges);
+    merger.bulk_push(&[&test_square; 4]);
+
+    assert_eq!(
+        merger.get_canvas().dimensions(),
+        ((IMAGE_WIDTH + 5) * 2, (IMAGE_HEIGHT + 20) * 2)
+    );
+
+    let rects: Vec<_> = merger.cell_rects().collect();
+    assert_eq!(rects[0].1, Point { x: 5, y: 20 });
+    assert_eq!(
+        rects[3].1,
+        Point {
+            x: IMAGE_WIDTH + 10,
+            y: IMAGE_HEIGHT + 40
+        }
+    );
+
+    assert_eq!(*merger.get_canvas().get_pixel(4, 19), Rgba([0, 0, 0, 0]));
+    assert_eq!(
+        merger.get_canvas().get_pixel(5, 20),
+        test_square.get_pixel(0, 0)
+    );
+}
+
+#[test]
+fn test_draw_cell_borders() {
+    let color = Rgba([255, 0, 255, 255]);
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+
+    // With padding, the borders are drawn in the gutters around each image.
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        2,
+        4,
+        Some(Padding {
+            x: PADDING_X,
+            y: PADDING_Y,
+        }),
+    );
+    merger.bulk_push(&[&test_square; 4]);
+    merger.draw_cell_borders(color, 2);
+
+    let canvas = merger.get_canvas();
+    let cell_x = IMAGE_WIDTH + PADDING_X;
+    for y in 0..IMAGE_HEIGHT {
+        assert_eq!(*canvas.get_pixel(cell_x - 1, y), color);
+        assert_eq!(*canvas.get_pixel(cell_x - 2, y), color);
+        assert_eq!(canvas.get_pixel(cell_x, y), test_square.get_pixel(0, y));
+    }
+    assert_eq!(
+        *canvas.get_pixel(cell_x - 3, 0),
+        Rgba([0, 0, 0, 0]),
+        "borders should not exceed their thickness"
+    );
+
+    // Without padding, the borders are drawn over the edges of each image.
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    merger.bulk_push(&[&test_square; 4]);
+    merger.draw_cell_borders(color, 1);
+
+    let canvas = merger.get_canvas();
+    for x in 0..IMAGE_WIDTH * 2 {
+        assert_eq!(*canvas.get_pixel(x, IMAGE_HEIGHT), color);
+        assert_eq!(*canvas.get_pixel(x, IMAGE_HEIGHT - 1), color);
+    }
+    assert_eq!(canvas.get_pixel(1, 1), test_square.get_pixel(1, 1));
+}
+
+#[test]
+fn test_push_at() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let red_square =
+        RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([255, 0, 0, 255]));
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 9, None);
+    merger.bulk_push(&[&test_square; 9]);
+
+    merger.push_at(5, &red_square).unwrap();
+    assert_eq!(merger.get_num_images(), 9);
+
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 9, None);
+    expected.bulk_push(&[&test_square; 5]);
+    expected.push(&red_square);
+    expected.bulk_push(&[&test_square; 3]);
+    assert_eq!(merger.get_canvas(), expected.get_canvas());
+
+    assert!(matches!(
+        merger.push_at(9, &red_square),
+        Err(MergerError::IndexOutOfBounds {
+            index: 9,
+            capacity: 9
+        })
+    ));
+}
+
+#[test]
+fn test_push_at_empty_cell() {
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 3, 9, None);
+
+    let marker = RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([1, 2, 3, 255]));
+    merger.push_at(4, &marker).unwrap();
+    assert_eq!(merger.get_num_images(), 1);
+
+    merger.push(&test_square);
+    assert_eq!(merger.get_num_images(), 2);
+
+    let indices: Vec<u32> = merger.cell_rects().map(|(index, _, _)| index).collect();
+    assert_eq!(indices, vec![0, 4]);
+
+    // Pushes carry on until they reach the cell filled out of order, then skip over it.
+    merger.bulk_push(&[&test_square; 3]);
+    assert_eq!(merger.push_indexed(&test_square), 5);
+    assert_eq!(merger.get_num_images(), 6);
+    assert_eq!(merger.remaining_capacity(), 3);
+    assert_eq!(
+        *merger.get_canvas().get_pixel(IMAGE_WIDTH, IMAGE_HEIGHT),
+        Rgba([1, 2, 3, 255])
+    );
+
+    let small = RgbaImageBuffer::new(IMAGE_WIDTH / 2, IMAGE_HEIGHT);
+    assert!(matches!(
+        merger.push_at(0, &small),
+        Err(MergerError::DimensionMismatch { index: None, .. })
+    ));
+}
+
+#[test]
+fn test_push_tinted() {
+    let tile =
+        RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, Rgba([200, 100, 50, 255]));
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    merger.push_tinted(&tile, 0.5, None);
+    merger.push_tinted(&tile, 0.5, Some(Rgba([0, 0, 250, 255])));
+    merger.push_tinted(&tile, 1.0, None);
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(0, 0), Rgba([100, 50, 25, 128]));
+    assert_eq!(*canvas.get_pixel(IMAGE_WIDTH, 0), Rgba([100, 50, 150, 255]));
+
+    let mut expected: KnownSizeMerger<Rgba<u8>, _> =
+        KnownSizeMerger::new((IMAGE_WIDTH, IMAGE_HEIGHT), 2, 4, None);
+    expected.push(&tile);
+    assert_eq!(
+        *canvas.get_pixel(0, IMAGE_HEIGHT),
+        *expected.get_canvas().get_pixel(0, 0)
+    );
+}
+
+#[test]
+fn test_push_masked() {
+    let background = Rgba([0, 0, 255, 255]);
+    let red = Rgba([255, 0, 0, 255]);
+    let tile = RgbaImageBuffer::new_from_pixel(IMAGE_WIDTH, IMAGE_HEIGHT, red);
+
+    let mut mask: BufferedImage<Luma<u8>> = BufferedImage::new(IMAGE_WIDTH, IMAGE_HEIGHT);
+    for (x, _, pixel) in mask.enumerate_pixels_mut() {
+        *pixel = if x < IMAGE_WIDTH / 2 {
+            Luma([0])
+        } else {
+            Luma([255])
+        };
+    }
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::builder()
+        .image_dimensions((IMAGE_WIDTH, IMAGE_HEIGHT))
+        .images_per_row(2)
+        .total_images(2)
+        .background(background)
+        .build();
+    merger.push_masked(&tile, &mask).unwrap();
+
+    let canvas = merger.get_canvas();
+    for y in 0..IMAGE_HEIGHT {
+        assert_eq!(*canvas.get_pixel(IMAGE_WIDTH / 2 - 1, y), background);
+        assert_eq!(*canvas.get_pixel(IMAGE_WIDTH / 2, y), red);
+    }
+
+    let small_mask: BufferedImage<Luma<u8>> = BufferedImage::new(10, 10);
+    assert!(matches!(
+        merger.push_masked(&tile, &small_mask),
+        Err(MergerError::DimensionMismatch { index: None, expected, got: (10, 10) }) if expected == (IMAGE_WIDTH, IMAGE_HEIGHT)
+    ));
+    let small_tile = RgbaImageBuffer::new_from_pixel(10, 10, red);
+    assert!(matches!(
+        merger.push_masked(&small_tile, &small_mask),
+        Err(MergerError::DimensionMismatch { index: None, expected, got: (10, 10) }) if expected == (IMAGE_WIDTH, IMAGE_HEIGHT)
+    ));
+    assert_eq!(merger.get_num_images(), 1);
+
+    // A merger over a borrowed buffer takes tiles with their own containers.
+    let (width, height) = merger.get_canvas().dimensions();
+    let mut buffer = vec![0u8; (width * height * 4) as usize];
+    let mut borrowed: KnownSizeMerger<Rgba<u8>, &mut [u8]> = KnownSizeMerger::new_from_raw(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        2,
+        2,
+        None,
+        buffer.as_mut_slice(),
+    )
+    .unwrap();
+    borrowed.push_masked(&tile, &mask).unwrap();
+    assert_eq!(*borrowed.get_canvas().get_pixel(IMAGE_WIDTH / 2, 0), red);
+}
+
+#[test]
+fn test_push_transformed() {
+    let mut tile = RgbaImageBuffer::new(4, 2);
+    for (x, y, pixel) in tile.enumerate_pixels_mut() {
+        *pixel = Rgba([x as u8, y as u8, 0, 255]);
+    }
+
+    let mut merger: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new((4, 2), 2, 2, None);
+    merger.push_transformed(&tile, Transform::FlipH).unwrap();
+    assert!(matches!(
+        merger.push_transformed(&tile, Transform::Rotate90),
+        Err(MergerError::DimensionMismatch {
+            index: None,
+            expected: (4, 2),
+            got: (2, 4)
+        })
+    ));
+    merger
+        .push_transformed(&tile, Transform::Rotate180)
+        .unwrap();
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(0, 0), Rgba([3, 0, 0, 255]));
+    assert_eq!(*canvas.get_pixel(4, 0), Rgba([3, 1, 0, 255]));
+    assert_eq!(merger.get_num_images(), 2);
+}
+
+#[test]
+fn test_strip() {
+    let padding = Some(Padding {
+        x: PADDING_X,
+        y: PADDING_Y,
+    });
+    let test_square = RgbaImageBuffer::test_gradient(IMAGE_WIDTH, IMAGE_HEIGHT);
+
+    let mut horizontal: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new_strip(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        5,
+        padding,
+        Orientation::Horizontal,
+    );
+    horizontal.bulk_push(&[&test_square; 5]);
+    assert_eq!(
+        horizontal.get_canvas().dimensions(),
+        (5 * IMAGE_WIDTH + 4 * PADDING_X, IMAGE_HEIGHT)
+    );
+
+    let vertical: KnownSizeMerger<Rgba<u8>, _> = KnownSizeMerger::new_strip(
+        (IMAGE_WIDTH, IMAGE_HEIGHT),
+        5,
+        padding,
+        Orientation::Vertical,
+    );
+    assert_eq!(
+        vertical.get_canvas().dimensions(),
+        (IMAGE_WIDTH, 5 * IMAGE_HEIGHT + 4 * PADDING_Y)
+    );
+}
+
+#[test]
+fn test_non_uniform_merger() {
+    let wide = RgbaImageBuffer::new_from_pixel(100, 40, Rgba([255, 0, 0, 255]));
+    let narrow = RgbaImageBuffer::new_from_pixel(50, 40, Rgba([0, 255, 0, 255]));
+
+    let mut merger: NonUniformMerger<Rgba<u8>> = NonUniformMerger::new(vec![100, 50], vec![40, 60]);
+    merger.bulk_push(&[&wide, &narrow]);
+    merger.push(&narrow);
+
+    assert_eq!(merger.get_canvas().dimensions(), (150, 100));
+    assert_eq!(merger.get_cell(1).unwrap().0, Point { x: 100, y: 0 });
+
+    let canvas = merger.get_canvas();
+    assert_eq!(*canvas.get_pixel(99, 0), Rgba([255, 0, 0, 255]));
+    assert_eq!(*canvas.get_pixel(100, 0), Rgba([0, 255, 0, 255]));
+    assert_eq!(*canvas.get_pixel(0, 40), Rgba([0, 255, 0, 255]));
+
+    // The wide image does not fit in the narrow column.
+    assert!(matches!(
+        merger.try_push(&wide),
+        Err(MergerError::DimensionMismatch { .. })
+    ));
+    assert_eq!(merger.get_num_images(), 3);
+}